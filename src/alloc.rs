@@ -0,0 +1,301 @@
+use std::{alloc::Layout, fmt, ptr};
+
+/// Error returned by a [`LeAllocator`] that could not hand out memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+/// A memory allocator that [`LeVec`](crate::LeVec) can be built on.
+///
+/// This is a stable stand-in for the unstable `std::alloc::Allocator`. Layouts may be
+/// zero-sized: an implementation can answer them with a dangling, well aligned pointer as
+/// long as it accepts that pointer back in `deallocate`, `grow` and `shrink`.
+///
+/// # Safety
+///
+/// Memory returned by `allocate`, `grow` and `shrink` must stay valid until it is passed to
+/// `deallocate`, `grow` or `shrink`, must fit the requested layout, and must not be handed
+/// out twice. Moving the allocator must not invalidate the blocks it returned.
+pub unsafe trait LeAllocator {
+    /// Allocates a block of memory that fits `layout`.
+    fn allocate(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError>;
+
+    /// Allocates a block of memory that fits `layout`, with every byte set to zero.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+        let ptr = self.allocate(layout)?;
+        //SAFETY: the block was just allocated with layout.size() bytes
+        unsafe { ptr::write_bytes(ptr.as_ptr(), 0, layout.size()) };
+        Ok(ptr)
+    }
+
+    /// Frees a block of memory.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `layout`.
+    unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: Layout);
+
+    /// Moves a block into a bigger one, keeping its contents. On error the old block is
+    /// left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `old_layout`, `new_layout`
+    /// must have the same alignment and a size not smaller than `old_layout`.
+    unsafe fn grow(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    /// Moves a block into a smaller one, keeping the contents that fit. On error the old
+    /// block is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `old_layout`, `new_layout`
+    /// must have the same alignment and a size not bigger than `old_layout`.
+    unsafe fn shrink(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        debug_assert!(new_layout.size() <= old_layout.size());
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), new_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+}
+
+/// The global allocator, backed by `std::alloc`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Global;
+
+/// Returns the pointer `Global` hands out for zero-sized layouts, which owns no memory.
+fn dangling(layout: Layout) -> ptr::NonNull<u8> {
+    //SAFETY: alignments are never zero
+    unsafe { ptr::NonNull::new_unchecked(ptr::without_provenance_mut(layout.align())) }
+}
+
+unsafe impl LeAllocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(dangling(layout));
+        }
+        //SAFETY: the layout is not zero-sized
+        let ptr = unsafe { std::alloc::alloc(layout) };
+        ptr::NonNull::new(ptr).ok_or(AllocError)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(dangling(layout));
+        }
+        //SAFETY: the layout is not zero-sized
+        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+        ptr::NonNull::new(ptr).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+        /*
+           SAFETY:
+               ptr was allocated via this allocator
+               layout is the same layout that was used to allocate ptr
+        */
+        std::alloc::dealloc(ptr.as_ptr(), layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        if old_layout.size() == 0 {
+            return self.allocate(new_layout);
+        }
+        if new_layout.size() == 0 {
+            self.deallocate(ptr, old_layout);
+            return Ok(dangling(new_layout));
+        }
+        /*
+           SAFETY:
+               ptr was allocated via this allocator
+               old_layout is the same layout that was used to allocate ptr
+               new_layout has the same alignment and a valid non-zero size
+        */
+        let ptr = std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size());
+        ptr::NonNull::new(ptr).ok_or(AllocError)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        //SAFETY: same contract as grow
+        self.grow(ptr, old_layout, new_layout)
+    }
+}
+
+unsafe impl<A: LeAllocator + ?Sized> LeAllocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+        (**self).allocate_zeroed(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: ptr::NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<ptr::NonNull<u8>, AllocError> {
+        (**self).shrink(ptr, old_layout, new_layout)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{alloc::Layout, cell::Cell, ptr};
+
+    use super::*;
+    use crate::{LeVec, TryReserveError};
+
+    /// Counts the live bytes and calls going through the global allocator.
+    #[derive(Default)]
+    struct Tracking {
+        live: Cell<usize>,
+        calls: Cell<usize>,
+    }
+
+    unsafe impl LeAllocator for Tracking {
+        fn allocate(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+            self.live.set(self.live.get() + layout.size());
+            self.calls.set(self.calls.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - layout.size());
+            self.calls.set(self.calls.get() + 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    /// Refuses every allocation.
+    struct Failing;
+
+    unsafe impl LeAllocator for Failing {
+        fn allocate(&self, _layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _ptr: ptr::NonNull<u8>, _layout: Layout) {
+            unreachable!("nothing was ever allocated")
+        }
+    }
+
+    #[test]
+    fn test_tracking_allocator() {
+        let tracking = Tracking::default();
+
+        let mut vec = LeVec::new_in(&tracking);
+        vec.extend(0u32..5);
+        assert_eq!(tracking.live.get(), 5 * 4);
+
+        vec.push(5);
+        vec.shrink_to_fit();
+        assert_eq!(tracking.live.get(), 6 * 4);
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        drop(iter);
+        assert_eq!(tracking.live.get(), 0);
+
+        let vec = LeVec::<u64, _>::with_capacity_in(3, &tracking);
+        assert_eq!(tracking.live.get(), 3 * 8);
+        drop(vec);
+        assert_eq!(tracking.live.get(), 0);
+        assert!(tracking.calls.get() > 0);
+    }
+
+    #[test]
+    fn test_failing_allocator() {
+        let mut vec = LeVec::new_in(Failing);
+        let error = vec.try_push(1u16).unwrap_err();
+        assert_eq!(
+            error.error,
+            TryReserveError::AllocError {
+                layout: Layout::array::<u16>(4).unwrap()
+            }
+        );
+        assert_eq!(error.into_value(), 1);
+        assert!(vec.is_empty());
+        assert!(LeVec::<u16, _>::try_with_capacity_in(1, Failing).is_err());
+
+        // zero-sized types never reach the allocator
+        let mut vec = LeVec::new_in(Failing);
+        vec.push(());
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "allocation failed")]
+    fn test_failing_allocator_push() {
+        let mut vec = LeVec::new_in(Failing);
+        vec.push(1u16);
+    }
+
+    #[test]
+    fn test_global_zero_sized() {
+        let empty = Layout::from_size_align(0, 16).unwrap();
+        let ptr = Global.allocate(empty).unwrap();
+        assert_eq!(ptr.as_ptr() as usize % 16, 0);
+        assert_eq!(Global.allocate_zeroed(empty).unwrap(), ptr);
+
+        let layout = Layout::from_size_align(32, 16).unwrap();
+        //SAFETY: every block comes from Global with the layout it is passed back with
+        unsafe {
+            let ptr = Global.grow(ptr, empty, layout).unwrap();
+            ptr.as_ptr().write_bytes(7, 32);
+            let ptr = Global.shrink(ptr, layout, empty).unwrap();
+            Global.deallocate(ptr, empty);
+        }
+    }
+}
@@ -0,0 +1,691 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr,
+    slice::SliceIndex,
+};
+
+use crate::{shift, slice_range, CapacityError, LeVec};
+
+/// A vector with a fixed capacity of `CAP` elements, stored inline and never allocated.
+///
+/// Adding to a full vector hands the value back in a [`CapacityError`] instead of growing.
+/// Convert it into a [`LeVec`] when it has to grow beyond `CAP`.
+///
+/// ```
+/// use le_vec::{ArrayLeVec, LeVec};
+///
+/// let mut vec: ArrayLeVec<i32, 2> = ArrayLeVec::new();
+/// vec.push(1).unwrap();
+/// vec.push(2).unwrap();
+/// assert_eq!(vec.push(3).unwrap_err().into_value(), 3);
+///
+/// let mut vec = LeVec::from(vec);
+/// vec.push(3);
+/// assert_eq!(vec, [1, 2, 3]);
+/// ```
+pub struct ArrayLeVec<T, const CAP: usize> {
+    buf: [MaybeUninit<T>; CAP],
+    len: usize,
+}
+
+impl<T, const CAP: usize> ArrayLeVec<T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [const { MaybeUninit::uninit() }; CAP],
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    /// Returns how many more elements fit before the vector is full.
+    pub const fn remaining_capacity(&self) -> usize {
+        CAP - self.len
+    }
+
+    /// Appends `value`, or hands it back if the vector is full.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError(value));
+        }
+
+        self.buf[self.len].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is full.
+    pub fn push_unchecked(&mut self, value: T) {
+        if self.push(value).is_err() {
+            panic!("ArrayLeVec is full (capacity is {CAP})");
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        //SAFETY: the slot was initialized and is no longer covered by the length
+        Some(unsafe { self.buf[self.len].assume_init_read() })
+    }
+
+    /// Inserts `value` at `index`, shifting everything after it to the right, or hands it
+    /// back if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), CapacityError<T>> {
+        let len = self.len;
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        if self.is_full() {
+            return Err(CapacityError(value));
+        }
+
+        //SAFETY: index <= len < CAP, so both the shifted range and the slot are in bounds
+        unsafe {
+            let slot = self.as_mut_ptr().add(index);
+            ptr::copy(slot, slot.add(1), len - index);
+            slot.write(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, shifting everything after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        assert!(
+            index < len,
+            "removal index (is {index}) should be < len (is {len})"
+        );
+
+        //SAFETY: index is in bounds, the removed element is read once before being overwritten
+        unsafe {
+            let slot = self.as_mut_ptr().add(index);
+            let value = slot.read();
+            ptr::copy(slot.add(1), slot, len - index - 1);
+            self.len -= 1;
+            value
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        assert!(
+            index < len,
+            "swap_remove index (is {index}) should be < len (is {len})"
+        );
+
+        //SAFETY: index and len - 1 are both in bounds, the removed element is read once
+        unsafe {
+            let base = self.as_mut_ptr();
+            let value = base.add(index).read();
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.len -= 1;
+            value
+        }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest. Does nothing if `len` is
+    /// not lower than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let remaining = self.len - len;
+        //SAFETY: len..self.len is initialized and is no longer visible once the length shrinks
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), remaining);
+            self.len = len;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops every element.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator.
+    ///
+    /// The range is removed even if the iterator is not fully consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end or the end is greater
+    /// than the length.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> ArrayDrain<'_, T, CAP> {
+        let len = self.len;
+        let Range { start, end } = slice_range(range, len);
+
+        // every access goes through this pointer, so borrowing the length below does not
+        // invalidate the drained elements
+        let base = self.as_mut_ptr();
+        // only the prefix stays visible, a forgotten drain leaks the rest instead of double dropping it
+        self.len = start;
+
+        //SAFETY: start..end is in bounds and the elements stay initialized until the drain yields them
+        let drained = unsafe { std::slice::from_raw_parts(base.add(start), end - start) };
+        ArrayDrain {
+            //SAFETY: the pointer is derived from a reference
+            base: unsafe { ptr::NonNull::new_unchecked(base) },
+            len: &mut self.len,
+            iter: drained.iter(),
+            tail_start: end,
+            tail_len: len - end,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, in their original order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|value| f(value));
+    }
+
+    /// Like [`retain`](Self::retain), but `f` may modify the elements it visits.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        let base = self.as_mut_ptr();
+        //SAFETY: the first len elements are initialized and self is mutably borrowed
+        unsafe { shift::retain_mut(base, &mut self.len, f) };
+    }
+
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr().cast()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr().cast()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        //SAFETY: the first len elements are initialized
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //SAFETY: the first len elements are initialized
+        unsafe { std::slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+}
+
+impl<T, const CAP: usize> Drop for ArrayLeVec<T, CAP> {
+    fn drop(&mut self) {
+        //SAFETY: the first len elements are initialized
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T, const CAP: usize> From<[T; CAP]> for ArrayLeVec<T, CAP> {
+    fn from(array: [T; CAP]) -> Self {
+        let array = ManuallyDrop::new(array);
+        Self {
+            //SAFETY: MaybeUninit<T> has the layout of T, and array is never dropped
+            buf: unsafe { ptr::read(&*array as *const [T; CAP] as *const [MaybeUninit<T>; CAP]) },
+            len: CAP,
+        }
+    }
+}
+
+impl<T, const CAP: usize> From<ArrayLeVec<T, CAP>> for LeVec<T> {
+    /// Moves the elements to a heap buffer, which can grow beyond `CAP`.
+    fn from(mut vec: ArrayLeVec<T, CAP>) -> Self {
+        let len = vec.len;
+        let mut heap = LeVec::with_capacity(len);
+        //SAFETY: heap has room for len elements, vec gives up ownership by dropping its length
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr(), heap.as_mut_ptr(), len);
+            vec.len = 0;
+            heap.set_len(len);
+        }
+        heap
+    }
+}
+
+impl<T, const CAP: usize> Default for ArrayLeVec<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for ArrayLeVec<T, CAP> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for ArrayLeVec<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: Hash, const CAP: usize> Hash for ArrayLeVec<T, CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+impl_slice_eq! { [const CAP: usize, const M: usize] ArrayLeVec<T, CAP>, ArrayLeVec<U, M> }
+impl_slice_eq! { [const CAP: usize] ArrayLeVec<T, CAP>, [U] }
+impl_slice_eq! { [const CAP: usize] ArrayLeVec<T, CAP>, &[U] }
+impl_slice_eq! { [const CAP: usize, const M: usize] ArrayLeVec<T, CAP>, [U; M] }
+
+impl<T: Eq, const CAP: usize> Eq for ArrayLeVec<T, CAP> {}
+
+impl<T, const CAP: usize> AsRef<[T]> for ArrayLeVec<T, CAP> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const CAP: usize> AsMut<[T]> for ArrayLeVec<T, CAP> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const CAP: usize> Deref for ArrayLeVec<T, CAP> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for ArrayLeVec<T, CAP> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const CAP: usize> Index<I> for ArrayLeVec<T, CAP> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const CAP: usize> IndexMut<I> for ArrayLeVec<T, CAP> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+impl<T, const CAP: usize> FromIterator<T> for ArrayLeVec<T, CAP> {
+    /// # Panics
+    ///
+    /// Panics if the iterator yields more than `CAP` elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T, const CAP: usize> Extend<T> for ArrayLeVec<T, CAP> {
+    /// # Panics
+    ///
+    /// Panics if the elements do not fit in the remaining capacity.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_unchecked(value);
+        }
+    }
+}
+
+impl<T, const CAP: usize> IntoIterator for ArrayLeVec<T, CAP> {
+    type Item = T;
+    type IntoIter = ArrayIntoIter<T, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let vec = ManuallyDrop::new(self);
+        ArrayIntoIter {
+            //SAFETY: vec is never dropped, so the elements are moved out exactly once
+            buf: unsafe { ptr::read(&vec.buf) },
+            start: 0,
+            end: vec.len,
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a ArrayLeVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a mut ArrayLeVec<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An owning iterator over the elements of an [`ArrayLeVec`], yielded front to back.
+pub struct ArrayIntoIter<T, const CAP: usize> {
+    /// `buf[start..end]` are the elements that were not yielded yet.
+    buf: [MaybeUninit<T>; CAP],
+    start: usize,
+    end: usize,
+}
+
+impl<T, const CAP: usize> ArrayIntoIter<T, CAP> {
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        //SAFETY: start..end are initialized elements that were not yielded yet
+        unsafe { std::slice::from_raw_parts(self.buf.as_ptr().add(self.start).cast(), self.len()) }
+    }
+}
+
+impl<T, const CAP: usize> Iterator for ArrayIntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        //SAFETY: start is initialized and not yielded yet, it is skipped from now on
+        let value = unsafe { self.buf[self.start].assume_init_read() };
+        self.start += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for ArrayIntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        //SAFETY: end is initialized and not yielded yet, it is skipped from now on
+        Some(unsafe { self.buf[self.end].assume_init_read() })
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for ArrayIntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for ArrayIntoIter<T, CAP> {}
+
+impl<T, const CAP: usize> Drop for ArrayIntoIter<T, CAP> {
+    fn drop(&mut self) {
+        let remaining = ptr::slice_from_raw_parts_mut(
+            self.buf[self.start..self.end].as_mut_ptr().cast::<T>(),
+            self.end - self.start,
+        );
+        self.start = self.end;
+        //SAFETY: the remaining elements were not yielded and will not be read again
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+/// A draining iterator over a range of an [`ArrayLeVec`], created by
+/// [`ArrayLeVec::drain`].
+///
+/// Works like [`Drain`](crate::Drain): forgetting it leaks the range and the tail instead
+/// of dropping anything twice.
+pub struct ArrayDrain<'a, T, const CAP: usize> {
+    base: ptr::NonNull<T>,
+    len: &'a mut usize,
+    iter: std::slice::Iter<'a, T>,
+    tail_start: usize,
+    tail_len: usize,
+    _marker: PhantomData<&'a mut ArrayLeVec<T, CAP>>,
+}
+
+//SAFETY: ArrayDrain behaves like the `&mut ArrayLeVec` it was created from
+unsafe impl<T: Send, const CAP: usize> Send for ArrayDrain<'_, T, CAP> {}
+//SAFETY: shared access only hands out the remaining elements as &[T]
+unsafe impl<T: Sync, const CAP: usize> Sync for ArrayDrain<'_, T, CAP> {}
+
+impl<T, const CAP: usize> ArrayDrain<'_, T, CAP> {
+    /// Returns the elements that were not yielded yet as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> Iterator for ArrayDrain<'_, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        //SAFETY: the element is inside the drained range and is never read again
+        self.iter.next().map(|value| unsafe { ptr::read(value) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for ArrayDrain<'_, T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        //SAFETY: the element is inside the drained range and is never read again
+        self.iter
+            .next_back()
+            .map(|value| unsafe { ptr::read(value) })
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for ArrayDrain<'_, T, CAP> {}
+
+impl<T, const CAP: usize> FusedIterator for ArrayDrain<'_, T, CAP> {}
+
+impl<T, const CAP: usize> Drop for ArrayDrain<'_, T, CAP> {
+    fn drop(&mut self) {
+        let remaining = mem::take(&mut self.iter);
+        //SAFETY: the remaining elements and the tail are initialized and were not yielded,
+        // the vector's length ends before the drained range
+        unsafe {
+            shift::finish_drain(
+                self.base.as_ptr(),
+                self.len,
+                remaining,
+                self.tail_start,
+                self.tail_len,
+            )
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use super::*;
+
+    const EMPTY: ArrayLeVec<String, 4> = ArrayLeVec::new();
+
+    #[test]
+    fn test_push_full() {
+        let mut vec: ArrayLeVec<i32, 3> = ArrayLeVec::new();
+        assert_eq!(vec.capacity(), 3);
+        for i in 0..3 {
+            assert_eq!(vec.push(i), Ok(()));
+        }
+        assert!(vec.is_full());
+        assert_eq!(vec.push(3), Err(CapacityError(3)));
+        assert_eq!(vec.insert(0, 4), Err(CapacityError(4)));
+        assert_eq!(vec, [0, 1, 2]);
+
+        assert_eq!(vec.pop(), Some(2));
+        assert_eq!(vec.remaining_capacity(), 1);
+        vec.push_unchecked(5);
+        assert_eq!(vec, [0, 1, 5]);
+
+        let mut vec: ArrayLeVec<i32, 0> = ArrayLeVec::new();
+        assert_eq!(vec.push(1), Err(CapacityError(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "ArrayLeVec is full (capacity is 1)")]
+    fn test_push_unchecked_full() {
+        let mut vec: ArrayLeVec<i32, 1> = ArrayLeVec::new();
+        vec.push_unchecked(1);
+        vec.push_unchecked(2);
+    }
+
+    #[test]
+    fn test_vec_api() {
+        let mut vec = EMPTY;
+        vec.push_unchecked("b".to_string());
+        vec.insert(0, "a".to_string()).unwrap();
+        vec.insert(2, "d".to_string()).unwrap();
+        vec.insert(2, "c".to_string()).unwrap();
+        assert_eq!(vec, ["a", "b", "c", "d"]);
+        assert_eq!(vec.get(1).map(String::as_str), Some("b"));
+
+        assert_eq!(vec.remove(0), "a");
+        assert_eq!(vec.swap_remove(0), "b");
+        assert_eq!(vec, ["d", "c"]);
+        vec[1].push('!');
+        assert_eq!(vec.clone(), ["d", "c!"]);
+        assert_eq!(format!("{vec:?}"), r#"["d", "c!"]"#);
+
+        vec.truncate(1);
+        assert_eq!(vec, ["d"]);
+        vec.clear();
+        assert!(vec.is_empty());
+
+        let vec = ArrayLeVec::from([3, 1, 2]);
+        assert_eq!(vec.into_iter().rev().collect::<Vec<_>>(), [2, 1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 1) should be < len (is 1)")]
+    fn test_remove_out_of_bounds() {
+        let mut vec = ArrayLeVec::from([1]);
+        vec.remove(1);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut vec: ArrayLeVec<i32, 8> = (0..6).collect();
+        let mut drain = vec.drain(1..4);
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next_back(), Some(3));
+        assert_eq!(drain.as_slice(), [2]);
+        drop(drain);
+        assert_eq!(vec, [0, 4, 5]);
+
+        let value = Rc::new(());
+        let mut vec: ArrayLeVec<Rc<()>, 5> = (0..5).map(|_| Rc::clone(&value)).collect();
+        std::mem::forget(vec.drain(2..3));
+        assert_eq!(vec.len(), 2);
+        drop(vec);
+        // the drained element and the tail are leaked, never dropped twice
+        assert_eq!(Rc::strong_count(&value), 4);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut vec: ArrayLeVec<i32, 8> = (0..8).collect();
+        vec.retain(|value| value % 3 != 0);
+        assert_eq!(vec, [1, 2, 4, 5, 7]);
+
+        vec.retain_mut(|value| {
+            *value *= 2;
+            *value < 10
+        });
+        assert_eq!(vec, [2, 4, 8]);
+    }
+
+    #[test]
+    fn test_retain_panic() {
+        let value = Rc::new(());
+        let mut vec: ArrayLeVec<Rc<()>, 6> = (0..6).map(|_| Rc::clone(&value)).collect();
+
+        let mut visited = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            vec.retain(|_| {
+                visited += 1;
+                if visited == 4 {
+                    panic!("predicate panicked");
+                }
+                visited % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        // two of the three visited elements were removed, the rest was kept in place
+        assert_eq!(vec.len(), 4);
+        assert_eq!(Rc::strong_count(&value), 5);
+
+        drop(vec);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_drop() {
+        let value = Rc::new(());
+        let vec: ArrayLeVec<Rc<()>, 4> = (0..3).map(|_| Rc::clone(&value)).collect();
+        let mut iter = vec.into_iter();
+        iter.next();
+        assert_eq!(iter.as_slice().len(), 2);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_into_le_vec() {
+        let vec: ArrayLeVec<String, 2> = ["a".to_string(), "b".to_string()].into();
+        let mut vec = LeVec::from(vec);
+        vec.push("c".to_string());
+        assert_eq!(vec, ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,165 @@
+//! Conversions between [`LeVec`] and the std containers.
+//!
+//! [`LeVec`] and `Vec` both allocate `[T; capacity]` from the global allocator, so buffers
+//! can change hands without copying a single element.
+
+use std::{mem::ManuallyDrop, ptr, rc::Rc, sync::Arc};
+
+use crate::{GrowthPolicy, LeAllocator, LeVec};
+
+impl<T> LeVec<T> {
+    /// Converts the vector into a boxed slice, shrinking the buffer to fit first.
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        self.shrink_to_fit();
+        let (ptr, len, _) = self.into_raw_parts();
+        //SAFETY: the buffer came from the global allocator with the layout of [T; len]
+        unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)) }
+    }
+}
+
+impl<T> From<Vec<T>> for LeVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        //SAFETY: Vec allocates [T; capacity] from the global allocator, just like LeVec
+        unsafe { LeVec::from_raw_parts(vec.as_mut_ptr(), vec.len(), vec.capacity()) }
+    }
+}
+
+impl<T> From<LeVec<T>> for Vec<T> {
+    fn from(vec: LeVec<T>) -> Self {
+        let (ptr, len, cap) = vec.into_raw_parts();
+        //SAFETY: LeVec allocates [T; capacity] from the global allocator, just like Vec
+        unsafe { Vec::from_raw_parts(ptr, len, cap) }
+    }
+}
+
+impl<T> From<Box<[T]>> for LeVec<T> {
+    fn from(slice: Box<[T]>) -> Self {
+        let len = slice.len();
+        let ptr = Box::into_raw(slice) as *mut T;
+        //SAFETY: a boxed slice is a global allocation of [T; len], or dangling when empty
+        unsafe { LeVec::from_raw_parts(ptr, len, len) }
+    }
+}
+
+impl<T> From<LeVec<T>> for Box<[T]> {
+    fn from(vec: LeVec<T>) -> Self {
+        vec.into_boxed_slice()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LeVec<T> {
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        let mut vec = LeVec::with_capacity(N);
+        //SAFETY: the space was reserved above and the array gives up its elements
+        unsafe {
+            ptr::copy_nonoverlapping(array.as_ptr(), vec.as_mut_ptr(), N);
+            vec.set_len(N);
+        }
+        vec
+    }
+}
+
+impl<T: Clone> From<&[T]> for LeVec<T> {
+    fn from(slice: &[T]) -> Self {
+        let mut vec = LeVec::with_capacity(slice.len());
+        vec.extend_from_slice(slice);
+        vec
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy, const N: usize> TryFrom<LeVec<T, A, G>> for [T; N] {
+    type Error = LeVec<T, A, G>;
+
+    /// Moves the elements into an array, giving the vector back if its length is not `N`.
+    fn try_from(mut vec: LeVec<T, A, G>) -> Result<Self, Self::Error> {
+        if vec.len() != N {
+            return Err(vec);
+        }
+
+        //SAFETY: the N elements are moved out once and forgotten by the vector, which then
+        //only frees its buffer
+        unsafe {
+            vec.set_len(0);
+            Ok(ptr::read(vec.as_ptr() as *const [T; N]))
+        }
+    }
+}
+
+impl<T> From<LeVec<T>> for Rc<[T]> {
+    fn from(vec: LeVec<T>) -> Self {
+        Rc::from(Vec::from(vec))
+    }
+}
+
+impl<T> From<LeVec<T>> for Arc<[T]> {
+    fn from(vec: LeVec<T>) -> Self {
+        Arc::from(Vec::from(vec))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::LeVec;
+
+    #[test]
+    fn test_vec_round_trip() {
+        let vec = vec!["a".to_string(), "b".to_string()];
+        let ptr = vec.as_ptr();
+
+        let mut le_vec = LeVec::from(vec);
+        assert_eq!(le_vec.as_ptr(), ptr);
+        le_vec.push("c".to_string());
+
+        let vec: Vec<String> = le_vec.into();
+        assert_eq!(vec, ["a", "b", "c"]);
+
+        let empty: Vec<u32> = LeVec::new().into();
+        assert!(empty.is_empty());
+        let zero_sized: Vec<()> = LeVec::from(vec![(), ()]).into();
+        assert_eq!(zero_sized.len(), 2);
+    }
+
+    #[test]
+    fn test_boxed_slice() {
+        let boxed: Box<[i32]> = Box::new([1, 2, 3]);
+        let ptr = boxed.as_ptr();
+
+        let mut vec = LeVec::from(boxed);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec.capacity(), 3);
+
+        vec.reserve(10);
+        vec.push(4);
+        let boxed = vec.into_boxed_slice();
+        assert_eq!(&*boxed, [1, 2, 3, 4]);
+
+        let empty: Box<[String]> = LeVec::new().into();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_arrays_and_slices() {
+        let vec = LeVec::from(["a".to_string(), "b".to_string()]);
+        assert_eq!(vec, ["a", "b"]);
+
+        let array: [String; 2] = vec.try_into().unwrap();
+        assert_eq!(array, ["a", "b"]);
+
+        let vec = LeVec::from(&[1, 2, 3][..]);
+        let vec = <[i32; 2]>::try_from(vec).unwrap_err();
+        assert_eq!(vec, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_shared_slices() {
+        let rc: Rc<[i32]> = LeVec::from([1, 2]).into();
+        assert_eq!(&*rc, [1, 2]);
+
+        let arc: Arc<[i32]> = LeVec::from([3]).into();
+        assert_eq!(&*arc, [3]);
+    }
+}
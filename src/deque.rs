@@ -0,0 +1,970 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Index, IndexMut, Range, RangeBounds},
+    ptr,
+};
+
+use crate::{
+    raw::RawLeVec, slice_range, Doubling, Global, GrowthPolicy, LeAllocator, LeVec, TryReserveError,
+};
+
+/// A double-ended queue stored as a ring buffer, growing like a [`LeVec`].
+///
+/// The elements start at `head` and wrap around the end of the buffer, so they are seen as
+/// up to two slices. Pushing and popping at either end is O(1).
+///
+/// ```
+/// use le_vec::LeVecDeque;
+///
+/// let mut queue = LeVecDeque::new();
+/// queue.push_back(2);
+/// queue.push_back(3);
+/// queue.push_front(1);
+/// assert_eq!(queue, [1, 2, 3]);
+///
+/// assert_eq!(queue.pop_front(), Some(1));
+/// assert_eq!(queue.pop_back(), Some(3));
+/// assert_eq!(queue[0], 2);
+/// ```
+pub struct LeVecDeque<T, A: LeAllocator = Global> {
+    head: usize,
+    len: usize,
+    buf: RawLeVec<T, A>,
+    /// Tells drop check that the deque owns and drops values of `T`.
+    _marker: PhantomData<T>,
+}
+
+impl<T> LeVecDeque<T> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates an empty deque with room for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: LeAllocator> LeVecDeque<T, A> {
+    /// Creates an empty deque that will allocate from `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        Self::from_buf(RawLeVec::new_in(alloc), 0, 0)
+    }
+
+    /// Creates an empty deque with room for at least `capacity` elements, allocated from
+    /// `alloc`.
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::from_buf(RawLeVec::with_capacity_in(capacity, alloc), 0, 0)
+    }
+
+    /// Wraps a buffer holding `len` initialized elements starting at `head`.
+    fn from_buf(buf: RawLeVec<T, A>, head: usize, len: usize) -> Self {
+        Self {
+            head,
+            len,
+            buf,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the allocator the buffer is allocated from.
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Maps a position in the ring to a slot of the buffer, for positions below twice the
+    /// capacity.
+    fn wrap_index(&self, index: usize) -> usize {
+        let cap = self.capacity();
+        if index >= cap {
+            index - cap
+        } else {
+            index
+        }
+    }
+
+    /// Returns the slot of the buffer that holds the element at `index`.
+    fn to_physical_idx(&self, index: usize) -> usize {
+        self.wrap_index(self.head.wrapping_add(index))
+    }
+
+    /// Returns the slots of the buffer that hold the elements in `range`, in order.
+    fn slice_ranges(&self, range: Range<usize>) -> (Range<usize>, Range<usize>) {
+        let len = range.end - range.start;
+        if len == 0 {
+            return (0..0, 0..0);
+        }
+
+        let start = self.to_physical_idx(range.start);
+        let head_len = self.capacity() - start;
+        if head_len >= len {
+            (start..start + len, 0..0)
+        } else {
+            (start..self.capacity(), 0..len - head_len)
+        }
+    }
+
+    /// Moves the elements that wrapped around after the buffer grew from `old_cap`, so they
+    /// follow each other again in the bigger ring.
+    fn handle_capacity_increase(&mut self, old_cap: usize) {
+        let new_cap = self.capacity();
+        if self.head <= old_cap - self.len {
+            // the elements did not wrap, nothing to do
+            return;
+        }
+
+        let head_len = old_cap - self.head;
+        let tail_len = self.len - head_len;
+        //SAFETY: both moves stay inside the new buffer and only cover initialized elements
+        unsafe {
+            let base = self.buf.ptr();
+            if head_len > tail_len && new_cap - old_cap >= tail_len {
+                // the wrapped part is the shorter one and fits right after the old end
+                ptr::copy_nonoverlapping(base, base.add(old_cap), tail_len);
+            } else {
+                // move the front part to the end of the new buffer
+                let new_head = new_cap - head_len;
+                ptr::copy(base.add(self.head), base.add(new_head), head_len);
+                self.head = new_head;
+            }
+        }
+    }
+
+    /// Makes room for one more element, for callers that found the buffer full.
+    fn grow(&mut self) {
+        let old_cap = self.capacity();
+        self.buf.grow_one::<Doubling>();
+        self.handle_capacity_increase(old_cap);
+    }
+
+    /// Reserves room for at least `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes or the allocation fails.
+    pub fn reserve(&mut self, additional: usize) {
+        let old_cap = self.capacity();
+        self.buf.reserve::<Doubling>(self.len, additional);
+        self.handle_capacity_increase(old_cap);
+    }
+
+    /// Fallible version of [`reserve`](Self::reserve), never panics or aborts.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let old_cap = self.capacity();
+        self.buf.try_reserve::<Doubling>(self.len, additional)?;
+        self.handle_capacity_increase(old_cap);
+        Ok(())
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        if self.is_full() {
+            self.grow();
+        }
+
+        //SAFETY: the deque is not full, so the slot after the last element is free
+        unsafe {
+            self.buf
+                .ptr()
+                .add(self.to_physical_idx(self.len))
+                .write(value)
+        };
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        if self.is_full() {
+            self.grow();
+        }
+
+        self.head = self.wrap_index(self.head.wrapping_sub(1).wrapping_add(self.capacity()));
+        //SAFETY: the deque is not full, so the slot before the first element is free
+        unsafe { self.buf.ptr().add(self.head).write(value) };
+        self.len += 1;
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        //SAFETY: the slot held the last element, which is no longer covered by the length
+        Some(unsafe { self.buf.ptr().add(self.to_physical_idx(self.len)).read() })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let old_head = self.head;
+        self.head = self.to_physical_idx(1);
+        self.len -= 1;
+        //SAFETY: the slot held the first element, which is no longer covered by the ring
+        Some(unsafe { self.buf.ptr().add(old_head).read() })
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            //SAFETY: index is in bounds, so its slot is initialized
+            Some(unsafe { &*self.buf.ptr().add(self.to_physical_idx(index)) })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            //SAFETY: index is in bounds, so its slot is initialized
+            Some(unsafe { &mut *self.buf.ptr().add(self.to_physical_idx(index)) })
+        } else {
+            None
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+
+    /// Swaps the elements at `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len;
+        assert!(i < len, "index (is {i}) should be < len (is {len})");
+        assert!(j < len, "index (is {j}) should be < len (is {len})");
+
+        let (i, j) = (self.to_physical_idx(i), self.to_physical_idx(j));
+        //SAFETY: both slots hold initialized elements, ptr::swap allows i == j
+        unsafe { ptr::swap(self.buf.ptr().add(i), self.buf.ptr().add(j)) };
+    }
+
+    /// Returns the elements in order, as the part up to the end of the buffer and the part
+    /// that wrapped around to its start.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.slice_ranges(0..self.len);
+        //SAFETY: both ranges are initialized and do not overlap
+        unsafe { (self.buffer_range(front), self.buffer_range(back)) }
+    }
+
+    /// Mutable version of [`as_slices`](Self::as_slices).
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.slice_ranges(0..self.len);
+        //SAFETY: both ranges are initialized and do not overlap
+        unsafe { (self.buffer_range_mut(front), self.buffer_range_mut(back)) }
+    }
+
+    /// # Safety
+    ///
+    /// `range` must only cover initialized slots of the buffer.
+    unsafe fn buffer_range(&self, range: Range<usize>) -> &[T] {
+        std::slice::from_raw_parts(self.buf.ptr().add(range.start), range.len())
+    }
+
+    /// # Safety
+    ///
+    /// `range` must only cover initialized slots of the buffer, and the returned slices must
+    /// not overlap while they are alive.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn buffer_range_mut(&self, range: Range<usize>) -> &mut [T] {
+        std::slice::from_raw_parts_mut(self.buf.ptr().add(range.start), range.len())
+    }
+
+    /// Moves the elements so they follow each other in the buffer, and returns them as one
+    /// slice. Does not move anything if they already do.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if std::mem::size_of::<T>() == 0 {
+            // every zero-sized value is the same, only the length matters
+            self.head = 0;
+        }
+
+        let cap = self.capacity();
+        if self.head <= cap - self.len {
+            //SAFETY: the elements do not wrap, so head..head + len is initialized
+            return unsafe { self.buffer_range_mut(self.head..self.head + self.len) };
+        }
+
+        let head_len = cap - self.head;
+        let tail_len = self.len - head_len;
+        let free = cap - self.len;
+        //SAFETY: every move stays inside the buffer and only covers initialized elements,
+        //the overlap of each copy is checked against the free space
+        unsafe {
+            let base = self.buf.ptr();
+            if free >= head_len {
+                // shift the wrapped part right and put the front part before it
+                ptr::copy(base, base.add(head_len), tail_len);
+                ptr::copy_nonoverlapping(base.add(self.head), base, head_len);
+                self.head = 0;
+            } else if free >= tail_len {
+                // shift the front part left and put the wrapped part after it
+                ptr::copy(
+                    base.add(self.head),
+                    base.add(self.head - tail_len),
+                    head_len,
+                );
+                ptr::copy_nonoverlapping(base, base.add(cap - tail_len), tail_len);
+                self.head -= tail_len;
+            } else {
+                // not enough room for either part, rotate the whole buffer instead
+                let slots = std::slice::from_raw_parts_mut(base.cast::<MaybeUninit<T>>(), cap);
+                slots.rotate_left(self.head);
+                self.head = 0;
+            }
+
+            self.buffer_range_mut(self.head..self.head + self.len)
+        }
+    }
+
+    /// Rotates the deque `n` places to the left, so the element at `n` becomes the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.len;
+        assert!(n <= len, "rotation (is {n}) should be <= len (is {len})");
+
+        if self.is_full() {
+            // the ring has no gap, so moving the head is enough
+            self.head = self.to_physical_idx(n);
+        } else {
+            self.make_contiguous().rotate_left(n);
+        }
+    }
+
+    /// Rotates the deque `n` places to the right, so the last `n` elements come first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.len;
+        assert!(n <= len, "rotation (is {n}) should be <= len (is {len})");
+
+        self.rotate_left(len - n);
+    }
+
+    pub fn iter(&self) -> DequeIter<'_, T> {
+        self.range(..)
+    }
+
+    pub fn iter_mut(&mut self) -> DequeIterMut<'_, T> {
+        self.range_mut(..)
+    }
+
+    /// Returns an iterator over the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end or the end is greater
+    /// than the length.
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> DequeIter<'_, T> {
+        let (front, back) = self.slice_ranges(slice_range(range, self.len));
+        //SAFETY: both ranges are initialized and do not overlap
+        unsafe {
+            DequeIter {
+                front: self.buffer_range(front).iter(),
+                back: self.buffer_range(back).iter(),
+            }
+        }
+    }
+
+    /// Mutable version of [`range`](Self::range).
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> DequeIterMut<'_, T> {
+        let (front, back) = self.slice_ranges(slice_range(range, self.len));
+        //SAFETY: both ranges are initialized and do not overlap
+        unsafe {
+            DequeIterMut {
+                front: self.buffer_range_mut(front).iter_mut(),
+                back: self.buffer_range_mut(back).iter_mut(),
+            }
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front.contains(value) || back.contains(value)
+    }
+
+    /// Shortens the deque to `len` elements, dropping the ones at the back. Does nothing if
+    /// `len` is not lower than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let (front, back) = self.slice_ranges(len..self.len);
+        self.len = len;
+        //SAFETY: the ranges were initialized and are no longer covered by the length
+        unsafe {
+            let front = ptr::slice_from_raw_parts_mut(self.buf.ptr().add(front.start), front.len());
+            let back = ptr::slice_from_raw_parts_mut(self.buf.ptr().add(back.start), back.len());
+            drop_both(front, back);
+        }
+    }
+
+    /// Drops every element, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+}
+
+/// Drops both slices, the second one even if dropping the first one panics.
+///
+/// # Safety
+///
+/// Both slices must be initialized and never be used again.
+unsafe fn drop_both<T>(front: *mut [T], back: *mut [T]) {
+    struct Dropper<T>(*mut [T]);
+
+    impl<T> Drop for Dropper<T> {
+        fn drop(&mut self) {
+            //SAFETY: the caller gave up the slice
+            unsafe { ptr::drop_in_place(self.0) }
+        }
+    }
+
+    let _back = Dropper(back);
+    ptr::drop_in_place(front);
+}
+
+impl<T, A: LeAllocator> Drop for LeVecDeque<T, A> {
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        //SAFETY: the elements are initialized, the buffer is freed by RawLeVec
+        unsafe { drop_both(front, back) }
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> From<LeVec<T, A, G>> for LeVecDeque<T, A> {
+    /// Takes over the buffer of `vec` without copying.
+    fn from(vec: LeVec<T, A, G>) -> Self {
+        let vec = ManuallyDrop::new(vec);
+        //SAFETY: vec is never dropped, so the buffer is moved out exactly once
+        let buf = unsafe { ptr::read(&vec.buf) };
+        Self::from_buf(buf, 0, vec.len)
+    }
+}
+
+impl<T, A: LeAllocator> From<LeVecDeque<T, A>> for LeVec<T, A> {
+    /// Hands the buffer over without copying, moving the elements only if they wrapped
+    /// around or do not start at the beginning of the buffer.
+    fn from(deque: LeVecDeque<T, A>) -> Self {
+        let mut deque = ManuallyDrop::new(deque);
+        deque.make_contiguous();
+        if deque.head != 0 {
+            //SAFETY: head..head + len is initialized and 0..len is inside the buffer
+            unsafe {
+                let base = deque.buf.ptr();
+                ptr::copy(base.add(deque.head), base, deque.len);
+            }
+        }
+
+        //SAFETY: deque is never dropped, so the buffer is moved out exactly once
+        let buf = unsafe { ptr::read(&deque.buf) };
+        LeVec::from_buf(buf, deque.len)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LeVecDeque<T> {
+    fn from(array: [T; N]) -> Self {
+        LeVec::from(array).into()
+    }
+}
+
+impl<T> Default for LeVecDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, A: LeAllocator + Clone> Clone for LeVecDeque<T, A> {
+    fn clone(&self) -> Self {
+        let mut deque = Self::with_capacity_in(self.len, self.allocator().clone());
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T: fmt::Debug, A: LeAllocator> fmt::Debug for LeVecDeque<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Hash, A: LeAllocator> Hash for LeVecDeque<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // same as a slice, wherever the elements wrap around
+        state.write_usize(self.len);
+        self.iter().for_each(|value| value.hash(state));
+    }
+}
+
+impl<T, U, A1: LeAllocator, A2: LeAllocator> PartialEq<LeVecDeque<U, A2>> for LeVecDeque<T, A1>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &LeVecDeque<U, A2>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U, A: LeAllocator> PartialEq<&[U]> for LeVecDeque<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &&[U]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U, A: LeAllocator, const N: usize> PartialEq<[U; N]> for LeVecDeque<T, A>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.len == N && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, A: LeAllocator> Eq for LeVecDeque<T, A> {}
+
+impl<T: PartialOrd, A: LeAllocator> PartialOrd for LeVecDeque<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, A: LeAllocator> Ord for LeVecDeque<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T, A: LeAllocator> Index<usize> for LeVecDeque<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.len;
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+impl<T, A: LeAllocator> IndexMut<usize> for LeVecDeque<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+impl<T> FromIterator<T> for LeVecDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, A: LeAllocator> Extend<T> for LeVecDeque<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T, A: LeAllocator> IntoIterator for LeVecDeque<T, A> {
+    type Item = T;
+    type IntoIter = DequeIntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DequeIntoIter { deque: self }
+    }
+}
+
+impl<'a, T, A: LeAllocator> IntoIterator for &'a LeVecDeque<T, A> {
+    type Item = &'a T;
+    type IntoIter = DequeIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: LeAllocator> IntoIterator for &'a mut LeVecDeque<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = DequeIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the elements of a [`LeVecDeque`], created by [`LeVecDeque::iter`] and
+/// [`LeVecDeque::range`].
+#[derive(Clone)]
+pub struct DequeIter<'a, T> {
+    front: std::slice::Iter<'a, T>,
+    back: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for DequeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(value) => Some(value),
+            None => {
+                // the front part is done, keep going with the wrapped one
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for DequeIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(value) => Some(value),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.back.next_back()
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for DequeIter<'_, T> {}
+
+impl<T> FusedIterator for DequeIter<'_, T> {}
+
+/// A mutable iterator over the elements of a [`LeVecDeque`], created by
+/// [`LeVecDeque::iter_mut`] and [`LeVecDeque::range_mut`].
+pub struct DequeIterMut<'a, T> {
+    front: std::slice::IterMut<'a, T>,
+    back: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for DequeIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(value) => Some(value),
+            None => {
+                // the front part is done, keep going with the wrapped one
+                mem::swap(&mut self.front, &mut self.back);
+                self.front.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for DequeIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(value) => Some(value),
+            None => {
+                mem::swap(&mut self.front, &mut self.back);
+                self.back.next_back()
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for DequeIterMut<'_, T> {}
+
+impl<T> FusedIterator for DequeIterMut<'_, T> {}
+
+/// An owning iterator over the elements of a [`LeVecDeque`], yielded front to back.
+pub struct DequeIntoIter<T, A: LeAllocator = Global> {
+    deque: LeVecDeque<T, A>,
+}
+
+impl<T, A: LeAllocator> Iterator for DequeIntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T, A: LeAllocator> DoubleEndedIterator for DequeIntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, A: LeAllocator> ExactSizeIterator for DequeIntoIter<T, A> {}
+
+impl<T, A: LeAllocator> FusedIterator for DequeIntoIter<T, A> {}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use super::*;
+    use crate::test_util::PanicOnDrop;
+
+    /// A deque of capacity 8 holding `0..len`, starting at slot `head`.
+    fn wrapped(head: usize, len: usize) -> LeVecDeque<i32> {
+        let mut deque = LeVecDeque::with_capacity(8);
+        for _ in 0..head {
+            deque.push_back(-1);
+            deque.pop_front();
+        }
+        deque.extend(0..len as i32);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(deque.head, head);
+        deque
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut deque = LeVecDeque::new();
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+
+        for i in 0..10 {
+            deque.push_back(i);
+            deque.push_front(-i);
+        }
+        assert_eq!(deque.len(), 20);
+        assert_eq!(deque.front(), Some(&-9));
+        assert_eq!(deque.back(), Some(&9));
+        assert_eq!(deque[10], 0);
+
+        for i in (0..10).rev() {
+            assert_eq!(deque.pop_front(), Some(-i));
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn test_grow_wrapped() {
+        // every layout of a full ring, so each case of handle_capacity_increase runs
+        for head in 0..8 {
+            let mut deque = wrapped(head, 8);
+            deque.push_back(8);
+            deque.push_front(-1);
+            assert!(deque.iter().copied().eq(-1..=8), "head {head}");
+        }
+
+        let mut deque = wrapped(6, 4);
+        deque.reserve(10);
+        assert!(deque.capacity() >= 14);
+        assert_eq!(deque, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let deque = wrapped(6, 5);
+        assert_eq!(deque.as_slices(), (&[0, 1][..], &[2, 3, 4][..]));
+
+        let deque = wrapped(2, 5);
+        assert_eq!(deque.as_slices(), (&[0, 1, 2, 3, 4][..], &[][..]));
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        for head in 0..8 {
+            for len in 0..=8 {
+                let mut deque = wrapped(head, len);
+                let expected: Vec<i32> = (0..len as i32).collect();
+                assert_eq!(deque.make_contiguous(), expected, "head {head}, len {len}");
+                assert_eq!(deque.as_slices().1, [], "head {head}, len {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        for head in 0..8 {
+            for len in [5, 8] {
+                let mut deque = wrapped(head, len);
+                deque.rotate_left(2);
+                let mut expected: Vec<i32> = (0..len as i32).collect();
+                expected.rotate_left(2);
+                assert!(deque.iter().eq(expected.iter()), "head {head}, len {len}");
+
+                deque.rotate_right(3);
+                expected.rotate_right(3);
+                assert!(deque.iter().eq(expected.iter()), "head {head}, len {len}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation (is 3) should be <= len (is 2)")]
+    fn test_rotate_out_of_bounds() {
+        let mut deque = LeVecDeque::from([1, 2]);
+        deque.rotate_left(3);
+    }
+
+    #[test]
+    fn test_range_and_index() {
+        let mut deque = wrapped(6, 6);
+        assert!(deque.range(1..4).copied().eq(1..4));
+        assert!(deque.range(..).rev().copied().eq((0..6).rev()));
+        assert_eq!(deque.range(3..3).len(), 0);
+
+        for value in deque.range_mut(4..) {
+            *value *= 10;
+        }
+        deque[0] = -1;
+        deque.swap(1, 5);
+        assert_eq!(deque, [-1, 50, 2, 3, 40, 1]);
+        assert!(deque.contains(&40));
+        assert_eq!(deque.get(6), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn test_index_out_of_bounds() {
+        let deque = LeVecDeque::from([1, 2]);
+        let _ = deque[2];
+    }
+
+    #[test]
+    fn test_le_vec_conversion() {
+        let mut vec = LeVec::with_capacity(8);
+        vec.extend_from_copy_slice(&[1, 2, 3]);
+        let ptr = vec.as_ptr();
+
+        let mut deque = LeVecDeque::from(vec);
+        deque.push_front(0);
+        deque.push_back(4);
+        let vec = LeVec::from(deque);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+        let vec = LeVec::from(wrapped(5, 7));
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(vec.capacity(), 8);
+    }
+
+    #[test]
+    fn test_drop() {
+        let value = Rc::new(());
+        let mut deque = LeVecDeque::with_capacity(4);
+        for _ in 0..3 {
+            deque.push_back(Rc::clone(&value));
+            deque.push_front(Rc::clone(&value));
+        }
+        deque.truncate(2);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        let mut iter = deque.clone().into_iter();
+        iter.next();
+        assert_eq!(iter.len(), 1);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(deque);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_drop_panic() {
+        let value = Rc::new(());
+        let mut deque = LeVecDeque::with_capacity(4);
+        for i in 0..4 {
+            let value = PanicOnDrop {
+                panics: i == 3,
+                _counter: Rc::clone(&value),
+            };
+            if i < 2 {
+                deque.push_back(value);
+            } else {
+                deque.push_front(value);
+            }
+        }
+        assert_eq!(deque.as_slices().1.len(), 2);
+
+        // the panicking element starts the front slice, the back slice is still dropped
+        let result = catch_unwind(AssertUnwindSafe(|| drop(deque)));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut deque = LeVecDeque::new();
+        for _ in 0..5 {
+            deque.push_front(());
+            deque.push_back(());
+        }
+        assert_eq!(deque.len(), 10);
+        assert_eq!(deque.make_contiguous().len(), 10);
+        deque.rotate_left(3);
+        assert_eq!(deque.pop_front(), Some(()));
+        assert_eq!(deque.into_iter().count(), 9);
+    }
+}
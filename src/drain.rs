@@ -0,0 +1,153 @@
+use std::{iter::FusedIterator, marker::PhantomData, mem, ptr};
+
+use crate::{shift, Doubling, Global, GrowthPolicy, LeAllocator, LeVec};
+
+/// A draining iterator over a range of a [`LeVec`], created by [`LeVec::drain`].
+///
+/// While the `Drain` is alive the vector's length only covers the elements before the
+/// drained range. Dropping the `Drain` drops the elements that were not yielded and moves
+/// the tail back in place, so forgetting it leaks the range and the tail instead of
+/// dropping anything twice.
+pub struct Drain<'a, T, A: LeAllocator = Global, G: GrowthPolicy = Doubling> {
+    pub(crate) vec: ptr::NonNull<LeVec<T, A, G>>,
+    pub(crate) iter: std::slice::Iter<'a, T>,
+    pub(crate) tail_start: usize,
+    pub(crate) tail_len: usize,
+    pub(crate) _marker: PhantomData<&'a mut LeVec<T, A, G>>,
+}
+
+//SAFETY: Drain behaves like the `&mut LeVec` it was created from
+unsafe impl<T: Send, A: LeAllocator + Send, G: GrowthPolicy> Send for Drain<'_, T, A, G> {}
+//SAFETY: shared access only hands out the remaining elements as &[T]
+unsafe impl<T: Sync, A: LeAllocator + Sync, G: GrowthPolicy> Sync for Drain<'_, T, A, G> {}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Drain<'_, T, A, G> {
+    /// Returns the elements that were not yielded yet as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Iterator for Drain<'_, T, A, G> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        //SAFETY: the element is inside the drained range and is never read again
+        self.iter.next().map(|value| unsafe { ptr::read(value) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> DoubleEndedIterator for Drain<'_, T, A, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        //SAFETY: the element is inside the drained range and is never read again
+        self.iter
+            .next_back()
+            .map(|value| unsafe { ptr::read(value) })
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> ExactSizeIterator for Drain<'_, T, A, G> {}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> FusedIterator for Drain<'_, T, A, G> {}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Drop for Drain<'_, T, A, G> {
+    fn drop(&mut self) {
+        let remaining = mem::take(&mut self.iter);
+        //SAFETY: the vector is mutably borrowed for as long as the drain lives
+        let vec = unsafe { self.vec.as_mut() };
+        //SAFETY: the remaining elements and the tail are initialized and were not yielded,
+        // the vector's length ends before the drained range
+        unsafe {
+            shift::finish_drain(
+                vec.buf.ptr(),
+                &mut vec.len,
+                remaining,
+                self.tail_start,
+                self.tail_len,
+            )
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use crate::{test_util::PanicOnDrop, LeVec};
+
+    fn strings(values: &[&str]) -> LeVec<String> {
+        let mut vec = LeVec::new();
+        for value in values {
+            vec.push(value.to_string());
+        }
+        vec
+    }
+
+    #[test]
+    fn test_drain_range() {
+        let mut vec = strings(&["a", "b", "c", "d", "e", "f"]);
+
+        let mut drain = vec.drain(1..4);
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next().as_deref(), Some("b"));
+        assert_eq!(drain.next_back().as_deref(), Some("d"));
+        assert_eq!(drain.as_slice(), ["c"]);
+        drop(drain);
+
+        assert_eq!(vec.as_slice(), ["a", "e", "f"]);
+
+        let drained: Vec<_> = vec.drain(..).collect();
+        assert_eq!(drained, ["a", "e", "f"]);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn test_drain_forget() {
+        let value = Rc::new(());
+        let mut vec = LeVec::new();
+        for _ in 0..5 {
+            vec.push(Rc::clone(&value));
+        }
+
+        std::mem::forget(vec.drain(2..3));
+        assert_eq!(vec.len(), 2);
+        drop(vec);
+
+        // the drained element and the tail are leaked, never dropped twice
+        assert_eq!(Rc::strong_count(&value), 4);
+    }
+
+    #[test]
+    fn test_drain_panic_in_drop() {
+        let value = Rc::new(());
+        let mut vec = LeVec::new();
+        for i in 0..6 {
+            vec.push(PanicOnDrop {
+                panics: i == 2,
+                _counter: Rc::clone(&value),
+            });
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| drop(vec.drain(1..4))));
+        assert!(result.is_err());
+        assert_eq!(vec.len(), 3);
+        assert_eq!(Rc::strong_count(&value), 4);
+
+        drop(vec);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn test_drain_out_of_bounds() {
+        let mut vec = strings(&["a", "b", "c"]);
+        vec.drain(1..4);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{alloc::Layout, fmt};
+
+/// Error returned when an index does not point inside a [`LeVec`](crate::LeVec).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBoundsError {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index (is {}) should be < len (is {})",
+            self.index, self.len
+        )
+    }
+}
+
+impl std::error::Error for OutOfBoundsError {}
+
+/// Error returned by [`LeVec::try_insert`](crate::LeVec::try_insert) when `index > len`.
+///
+/// Holds the value that could not be inserted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertError<T> {
+    pub index: usize,
+    pub len: usize,
+    pub value: T,
+}
+
+impl<T> InsertError<T> {
+    /// Returns the value that could not be inserted.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "insertion index (is {}) should be <= len (is {})",
+            self.index, self.len
+        )
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for InsertError<T> {}
+
+/// Error returned when an [`ArrayLeVec`](crate::ArrayLeVec) has no room left.
+///
+/// Holds the value that could not be added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    /// Returns the value that could not be added.
+    pub fn into_value(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
+
+/// Error returned by the fallible allocation methods of [`LeVec`](crate::LeVec).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds `isize::MAX` bytes or overflows `usize`.
+    CapacityOverflow,
+    /// The allocator could not hand out memory for `layout`.
+    AllocError { layout: Layout },
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => f.write_str(
+                "memory allocation failed because the computed capacity exceeded the maximum",
+            ),
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+/// Error returned by [`LeVec::try_push`](crate::LeVec::try_push) when the buffer could not
+/// grow.
+///
+/// Holds the value that could not be pushed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryPushError<T> {
+    pub error: TryReserveError,
+    pub value: T,
+}
+
+impl<T> TryPushError<T> {
+    /// Returns the value that could not be pushed.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Display for TryPushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for TryPushError<T> {}
@@ -0,0 +1,121 @@
+use std::ptr;
+
+use crate::{Doubling, Global, GrowthPolicy, LeAllocator, LeVec};
+
+/// An iterator that removes the elements matching a predicate, created by
+/// [`LeVec::extract_if`].
+///
+/// Dropping it early keeps the elements that were not visited, in order.
+pub struct ExtractIf<'a, T, F, A: LeAllocator = Global, G: GrowthPolicy = Doubling>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    pub(crate) vec: &'a mut LeVec<T, A, G>,
+    /// The index of the next element to visit.
+    pub(crate) idx: usize,
+    /// The end of the range to visit.
+    pub(crate) end: usize,
+    /// The number of elements removed so far.
+    pub(crate) del: usize,
+    /// The length of the vector before extracting started.
+    pub(crate) old_len: usize,
+    pub(crate) pred: F,
+}
+
+impl<T, F, A: LeAllocator, G: GrowthPolicy> Iterator for ExtractIf<'_, T, F, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.end {
+            let i = self.idx;
+            //SAFETY: i < end <= old_len, elements from idx on were not moved or read yet
+            unsafe {
+                let current = self.vec.buf.ptr().add(i);
+                let extract = (self.pred)(&mut *current);
+                // only advanced after the predicate, so a panic keeps the current element
+                self.idx += 1;
+                if extract {
+                    self.del += 1;
+                    return Some(current.read());
+                } else if self.del > 0 {
+                    ptr::copy_nonoverlapping(current, current.sub(self.del), 1);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<T, F, A: LeAllocator, G: GrowthPolicy> Drop for ExtractIf<'_, T, F, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        if self.idx < self.old_len && self.del > 0 {
+            //SAFETY: idx..old_len is initialized and the gap of del slots before it is free
+            unsafe {
+                let base = self.vec.buf.ptr();
+                ptr::copy(
+                    base.add(self.idx),
+                    base.add(self.idx - self.del),
+                    self.old_len - self.idx,
+                );
+            }
+        }
+        self.vec.len = self.old_len - self.del;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::LeVec;
+
+    #[test]
+    fn test_extract_if() {
+        let mut vec: LeVec<i32> = (0..10).collect();
+
+        let evens: Vec<_> = vec.extract_if(.., |value| *value % 2 == 0).collect();
+        assert_eq!(evens, [0, 2, 4, 6, 8]);
+        assert_eq!(vec, [1, 3, 5, 7, 9]);
+
+        let extracted: Vec<_> = vec.extract_if(1..4, |value| *value > 3).collect();
+        assert_eq!(extracted, [5, 7]);
+        assert_eq!(vec, [1, 3, 9]);
+    }
+
+    #[test]
+    fn test_extract_if_early_drop() {
+        let mut vec: LeVec<String> = (0..8).map(|value| value.to_string()).collect();
+
+        let mut iter = vec.extract_if(.., |value| value.parse::<i32>().unwrap() % 3 == 0);
+        assert_eq!(iter.next().as_deref(), Some("0"));
+        assert_eq!(iter.next().as_deref(), Some("3"));
+        drop(iter);
+
+        assert_eq!(vec, ["1", "2", "4", "5", "6", "7"]);
+    }
+
+    #[test]
+    fn test_extract_if_panic() {
+        let mut vec: LeVec<i32> = (0..6).collect();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            vec.extract_if(.., |value| {
+                assert_ne!(*value, 3);
+                *value % 2 == 0
+            })
+            .for_each(drop)
+        }));
+        assert!(result.is_err());
+        assert_eq!(vec, [1, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,125 @@
+/// Decides how much a full [`LeVec`](crate::LeVec) grows.
+///
+/// Policies are chosen through the type parameter `G` of `LeVec<T, A, G>` and only run when
+/// the buffer has to grow, never for [`reserve_exact`](crate::LeVec::reserve_exact) or for
+/// zero-sized types. Whatever a policy returns is clamped to at least `required` and at
+/// most the largest capacity that fits in `isize::MAX` bytes.
+pub trait GrowthPolicy {
+    /// Returns the capacity to grow to, given the current capacity, the capacity that is
+    /// needed right now and the size of one element in bytes, which is never 0.
+    fn next_capacity(cap: usize, required: usize, elem_size: usize) -> usize;
+}
+
+/// Starts at 4 elements and doubles from there, the default policy.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+    fn next_capacity(cap: usize, required: usize, _elem_size: usize) -> usize {
+        cap.saturating_mul(2).max(required).max(4)
+    }
+}
+
+/// Starts at 4 elements and grows by half of the current capacity, trading a few more
+/// reallocations for less unused memory on big buffers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OneAndAHalf;
+
+impl GrowthPolicy for OneAndAHalf {
+    fn next_capacity(cap: usize, required: usize, _elem_size: usize) -> usize {
+        cap.saturating_add(cap / 2).max(required).max(4)
+    }
+}
+
+/// Grows by exactly `N` elements at a time, for buffers that should never overshoot by much.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FixedChunk<const N: usize>;
+
+impl<const N: usize> GrowthPolicy for FixedChunk<N> {
+    fn next_capacity(cap: usize, required: usize, _elem_size: usize) -> usize {
+        cap.saturating_add(N).max(required)
+    }
+}
+
+/// Doubles, then rounds the allocation up so no memory is wasted: to the next power of two
+/// below [`PageRounded::PAGE_SIZE`], matching the size classes of common allocators, and to
+/// whole pages above it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PageRounded;
+
+impl PageRounded {
+    pub const PAGE_SIZE: usize = 4096;
+}
+
+impl GrowthPolicy for PageRounded {
+    fn next_capacity(cap: usize, required: usize, elem_size: usize) -> usize {
+        let cap = Doubling::next_capacity(cap, required, elem_size);
+        let bytes = cap.saturating_mul(elem_size);
+        let rounded = if bytes < Self::PAGE_SIZE {
+            bytes.next_power_of_two()
+        } else {
+            bytes
+                .checked_next_multiple_of(Self::PAGE_SIZE)
+                .unwrap_or(bytes)
+        };
+        rounded / elem_size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LeVec, TryReserveError};
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(Doubling::next_capacity(0, 1, 8), 4);
+        assert_eq!(Doubling::next_capacity(4, 5, 8), 8);
+        assert_eq!(Doubling::next_capacity(4, 20, 8), 20);
+
+        assert_eq!(OneAndAHalf::next_capacity(0, 1, 8), 4);
+        assert_eq!(OneAndAHalf::next_capacity(8, 9, 8), 12);
+
+        assert_eq!(FixedChunk::<16>::next_capacity(0, 1, 8), 16);
+        assert_eq!(FixedChunk::<16>::next_capacity(16, 17, 8), 32);
+        assert_eq!(FixedChunk::<16>::next_capacity(16, 40, 8), 40);
+
+        // 4 * 12 bytes round up to the 64 byte size class
+        assert_eq!(PageRounded::next_capacity(0, 1, 12), 5);
+        // 800 * 8 bytes round up to two pages
+        assert_eq!(PageRounded::next_capacity(400, 401, 8), 1024);
+    }
+
+    #[test]
+    fn test_vec_with_policy() {
+        let mut vec = LeVec::new().with_growth::<OneAndAHalf>();
+        vec.extend_from_copy_slice(&[0u64; 4]);
+        assert_eq!(vec.capacity(), 4);
+        vec.push(4);
+        assert_eq!(vec.capacity(), 6);
+
+        let mut vec: LeVec<u8, _, FixedChunk<100>> = LeVec::default();
+        vec.push(1);
+        assert_eq!(vec.capacity(), 100);
+        vec.extend(0..100);
+        assert_eq!(vec.capacity(), 200);
+
+        let mut vec = LeVec::with_capacity(3).with_growth::<PageRounded>();
+        vec.extend_from_copy_slice(&[0u32; 4]);
+        assert_eq!(vec.capacity(), 8);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri aborts on the huge allocation instead of failing it
+    fn test_policy_isize_max() {
+        // the policy never goes past what fits in isize::MAX bytes
+        let mut vec = LeVec::<u64>::new().with_growth::<PageRounded>();
+        vec.push(0);
+        match vec.try_reserve(isize::MAX as usize / 8 - 1) {
+            Err(TryReserveError::AllocError { layout }) => {
+                assert_eq!(layout.size(), isize::MAX as usize / 8 * 8)
+            }
+            other => panic!("expected an allocation error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,649 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    mem::{self, ManuallyDrop},
+    num::NonZeroUsize,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::{IntoIter, LeVec};
+
+/// Decides which element of a [`LeBinaryHeap`] comes out first.
+///
+/// The heap pops the greatest element according to [`compare`](Self::compare). Closures
+/// taking two `&T` and returning an [`Ordering`] work as orders too.
+pub trait HeapOrder<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Pops the greatest element first, the default order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MaxHeap;
+
+impl<T: Ord> HeapOrder<T> for MaxHeap {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Pops the smallest element first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MinHeap;
+
+impl<T: Ord> HeapOrder<T> for MinHeap {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> HeapOrder<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// A priority queue stored as a binary heap in a [`LeVec`].
+///
+/// Pushing and popping are O(log n), peeking is O(1). The order comes from `C`, so a
+/// min-heap is a `LeBinaryHeap<T, MinHeap>`.
+///
+/// If comparing two elements panics, the heap may be left out of order, but it never loses
+/// or duplicates an element.
+///
+/// ```
+/// use le_vec::{LeBinaryHeap, MinHeap};
+///
+/// let mut heap = LeBinaryHeap::new();
+/// heap.push(3);
+/// heap.push(7);
+/// heap.push(1);
+/// assert_eq!(heap.peek(), Some(&7));
+/// assert_eq!(heap.pop(), Some(7));
+///
+/// let mut heap: LeBinaryHeap<i32, MinHeap> = [3, 7, 1].into_iter().collect();
+/// assert_eq!(heap.pop(), Some(1));
+///
+/// let mut heap = LeBinaryHeap::with_order(|a: &(u8, &str), b: &(u8, &str)| a.0.cmp(&b.0));
+/// heap.push((1, "later"));
+/// heap.push((2, "now"));
+/// assert_eq!(heap.pop(), Some((2, "now")));
+/// ```
+pub struct LeBinaryHeap<T, C = MaxHeap> {
+    data: LeVec<T>,
+    order: C,
+}
+
+impl<T: Ord> LeBinaryHeap<T> {
+    pub fn new() -> Self {
+        Self::with_order(MaxHeap)
+    }
+
+    /// Creates an empty heap with room for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_vec_with_order(LeVec::with_capacity(capacity), MaxHeap)
+    }
+}
+
+impl<T, C: HeapOrder<T>> LeBinaryHeap<T, C> {
+    /// Creates an empty heap that pops elements in `order`.
+    pub fn with_order(order: C) -> Self {
+        Self::from_vec_with_order(LeVec::new(), order)
+    }
+
+    /// Turns `vec` into a heap that pops elements in `order`, in O(n).
+    pub fn from_vec_with_order(vec: LeVec<T>, order: C) -> Self {
+        let mut heap = Self { data: vec, order };
+        heap.rebuild();
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves room for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Returns the element that would be popped next.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a guard to the element that would be popped next, which moves it to its new
+    /// place when dropped if it was modified.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut {
+                heap: self,
+                original_len: None,
+            })
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        let old_len = self.len();
+        self.data.push(value);
+        //SAFETY: old_len is the index of the element that was just pushed
+        unsafe { self.sift_up(0, old_len) };
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len > 1 {
+            // the root waits at the end while the last element sifts down, so it is still in
+            // the heap if a comparison panics
+            self.data.swap(0, len - 1);
+            //SAFETY: len - 1 is in bounds
+            unsafe { self.sift_down_range(0, len - 1) };
+        }
+        self.data.pop()
+    }
+
+    /// Moves every element of `other` in, leaving it empty.
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len() < other.len() {
+            // `other` may be ordered differently, so its elements only save a copy and the
+            // whole heap is rebuilt under `self.order`
+            mem::swap(&mut self.data, &mut other.data);
+            self.data.append(&mut other.data);
+            self.rebuild();
+            return;
+        }
+
+        let start = self.len();
+        self.data.append(&mut other.data);
+        self.rebuild_tail(start);
+    }
+
+    /// Returns the elements in no particular order.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns an iterator over the elements in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Drops every element, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the elements in no particular order.
+    pub fn into_vec(self) -> LeVec<T> {
+        self.data
+    }
+
+    /// Returns the elements sorted so the one that would be popped first comes last,
+    /// ascending for a [`MaxHeap`].
+    pub fn into_sorted_vec(mut self) -> LeVec<T> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+            //SAFETY: end is in bounds and greater than 0, so the sift covers a non-empty heap
+            unsafe {
+                let base = self.data.as_mut_ptr();
+                ptr::swap(base, base.add(end));
+                self.sift_down_range(0, end);
+            }
+        }
+        self.into_vec()
+    }
+
+    /// Moves the element at `pos` up until its parent comes before it, without going above
+    /// `start`. Returns its new position.
+    ///
+    /// # Safety
+    ///
+    /// `start <= pos < len`.
+    unsafe fn sift_up(&mut self, start: usize, pos: usize) -> usize {
+        let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+        while hole.pos() > start {
+            let parent = (hole.pos() - 1) / 2;
+            if self.order.compare(hole.element(), hole.get(parent)) != Ordering::Greater {
+                break;
+            }
+            hole.move_to(parent);
+        }
+        hole.pos()
+    }
+
+    /// Moves the element at `pos` down until both of its children within `..end` come
+    /// after it.
+    ///
+    /// # Safety
+    ///
+    /// `pos < end <= len`.
+    unsafe fn sift_down_range(&mut self, pos: usize, end: usize) {
+        let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+        let mut child = 2 * hole.pos() + 1;
+
+        // while both children are in range
+        while child <= end.saturating_sub(2) {
+            // pick the child that comes first
+            if self.order.compare(hole.get(child), hole.get(child + 1)) != Ordering::Greater {
+                child += 1;
+            }
+            if self.order.compare(hole.element(), hole.get(child)) != Ordering::Less {
+                return;
+            }
+            hole.move_to(child);
+            child = 2 * hole.pos() + 1;
+        }
+
+        // a last child without a sibling
+        if child == end - 1 && self.order.compare(hole.element(), hole.get(child)) == Ordering::Less
+        {
+            hole.move_to(child);
+        }
+    }
+
+    /// Restores the heap property over the whole vector, in O(n).
+    fn rebuild(&mut self) {
+        let mut n = self.len() / 2;
+        while n > 0 {
+            n -= 1;
+            //SAFETY: n < len / 2 is in bounds
+            unsafe { self.sift_down_range(n, self.len()) };
+        }
+    }
+
+    /// Restores the heap property after elements were added from `start` on, by sifting
+    /// them up one by one or rebuilding, whichever does less work.
+    fn rebuild_tail(&mut self, start: usize) {
+        let len = self.len();
+        if start == len {
+            return;
+        }
+
+        let tail_len = len - start;
+        // sifting up costs about tail_len * log2(start) comparisons, rebuilding about 2 * len
+        let log2_start = (usize::BITS - start.leading_zeros()).saturating_sub(1) as usize;
+        if start < tail_len || 2 * len < tail_len * log2_start {
+            self.rebuild();
+        } else {
+            for pos in start..len {
+                //SAFETY: pos is in bounds
+                unsafe { self.sift_up(0, pos) };
+            }
+        }
+    }
+}
+
+/// A slice with one element moved out, the hole, which is filled back in when dropped.
+///
+/// Sifting moves the hole instead of swapping elements, and the element is put back even
+/// if a comparison panics, so nothing is ever lost or duplicated.
+struct Hole<'a, T> {
+    data: &'a mut [T],
+    element: ManuallyDrop<T>,
+    pos: usize,
+}
+
+impl<'a, T> Hole<'a, T> {
+    /// # Safety
+    ///
+    /// `pos` must be in bounds.
+    unsafe fn new(data: &'a mut [T], pos: usize) -> Self {
+        let element = ptr::read(data.get_unchecked(pos));
+        Self {
+            data,
+            element: ManuallyDrop::new(element),
+            pos,
+        }
+    }
+
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the element that was moved out.
+    fn element(&self) -> &T {
+        &self.element
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be in bounds and not be the hole.
+    unsafe fn get(&self, index: usize) -> &T {
+        self.data.get_unchecked(index)
+    }
+
+    /// Moves the element at `index` into the hole, which moves to `index`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be in bounds and not be the hole.
+    unsafe fn move_to(&mut self, index: usize) {
+        let base = self.data.as_mut_ptr();
+        ptr::copy_nonoverlapping(base.add(index), base.add(self.pos), 1);
+        self.pos = index;
+    }
+}
+
+impl<T> Drop for Hole<'_, T> {
+    fn drop(&mut self) {
+        //SAFETY: pos is the hole, which is in bounds and holds no element
+        unsafe {
+            let pos = self.pos;
+            ptr::copy_nonoverlapping(&*self.element, self.data.as_mut_ptr().add(pos), 1);
+        }
+    }
+}
+
+/// A mutable reference to the first element of a [`LeBinaryHeap`], created by
+/// [`LeBinaryHeap::peek_mut`].
+///
+/// If the element was modified it is moved to its new place when the guard is dropped.
+pub struct PeekMut<'a, T, C: HeapOrder<T> = MaxHeap> {
+    heap: &'a mut LeBinaryHeap<T, C>,
+    /// The real length while the element may have been modified. The heap only shows its
+    /// first element meanwhile, so forgetting the guard leaks the rest instead of leaving
+    /// the heap out of order.
+    original_len: Option<NonZeroUsize>,
+}
+
+impl<T, C: HeapOrder<T>> PeekMut<'_, T, C> {
+    /// Removes the peeked element from the heap and returns it.
+    pub fn pop(mut this: Self) -> T {
+        if let Some(original_len) = this.original_len.take() {
+            //SAFETY: the elements past the first one were never touched
+            unsafe { this.heap.data.set_len(original_len.get()) };
+        }
+        // the guard is dropped without sifting, the heap pops from a valid state
+        this.heap
+            .pop()
+            .expect("PeekMut is only created for non-empty heaps")
+    }
+}
+
+impl<T, C: HeapOrder<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, C: HeapOrder<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let len = self.heap.len();
+        if len > 1 && self.original_len.is_none() {
+            //SAFETY: the heap gets its length back when the guard is dropped
+            unsafe { self.heap.data.set_len(1) };
+            self.original_len = NonZeroUsize::new(len);
+        }
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T, C: HeapOrder<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        if let Some(original_len) = self.original_len {
+            //SAFETY: the elements past the first one were never touched, and the first one
+            //is sifted back into place
+            unsafe {
+                self.heap.data.set_len(original_len.get());
+                self.heap.sift_down_range(0, original_len.get());
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug, C: HeapOrder<T>> fmt::Debug for PeekMut<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
+    }
+}
+
+impl<T, C: HeapOrder<T> + Default> From<LeVec<T>> for LeBinaryHeap<T, C> {
+    /// Turns `vec` into a heap in O(n).
+    fn from(vec: LeVec<T>) -> Self {
+        Self::from_vec_with_order(vec, C::default())
+    }
+}
+
+impl<T, C> From<LeBinaryHeap<T, C>> for LeVec<T> {
+    fn from(heap: LeBinaryHeap<T, C>) -> Self {
+        heap.data
+    }
+}
+
+impl<T, C: HeapOrder<T> + Default> Default for LeBinaryHeap<T, C> {
+    fn default() -> Self {
+        Self::with_order(C::default())
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for LeBinaryHeap<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            order: self.order.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for LeBinaryHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.data.as_slice(), f)
+    }
+}
+
+impl<T, C: HeapOrder<T> + Default> FromIterator<T> for LeBinaryHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        LeVec::from_iter(iter).into()
+    }
+}
+
+impl<T, C: HeapOrder<T>> Extend<T> for LeBinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.len();
+        self.data.extend(iter);
+        self.rebuild_tail(start);
+    }
+}
+
+impl<T, C> IntoIterator for LeBinaryHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Yields the elements in no particular order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a LeBinaryHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::Cell,
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use super::*;
+
+    fn drain_sorted<T, C: HeapOrder<T>>(mut heap: LeBinaryHeap<T, C>) -> Vec<T> {
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = LeBinaryHeap::new();
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+
+        for value in [5, 1, 8, 3, 9, 2, 8] {
+            heap.push(value);
+        }
+        assert_eq!(heap.len(), 7);
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(drain_sorted(heap), [9, 8, 8, 5, 3, 2, 1]);
+
+        let mut heap: LeBinaryHeap<i32, MinHeap> = LeBinaryHeap::default();
+        heap.extend([5, 1, 8, 3]);
+        assert_eq!(drain_sorted(heap), [1, 3, 5, 8]);
+    }
+
+    #[test]
+    fn test_heapify() {
+        let vec: LeVec<i32> = (0..100).map(|i| (i * 37) % 101).collect();
+        let mut expected: Vec<i32> = vec.iter().copied().collect();
+        expected.sort();
+
+        let heap: LeBinaryHeap<i32> = vec.clone().into();
+        assert_eq!(heap.clone().into_sorted_vec(), expected);
+        expected.reverse();
+        assert_eq!(drain_sorted(heap), expected);
+
+        let heap: LeBinaryHeap<i32, MinHeap> = vec.into();
+        assert_eq!(heap.into_sorted_vec().first(), Some(&100));
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut heap: LeBinaryHeap<i32> = [1, 5, 3].into_iter().collect();
+
+        // only reading does not move anything
+        assert_eq!(*heap.peek_mut().unwrap(), 5);
+        assert_eq!(heap.peek(), Some(&5));
+
+        *heap.peek_mut().unwrap() = 0;
+        assert_eq!(heap.peek(), Some(&3));
+        assert_eq!(heap.len(), 3);
+
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 3);
+        assert_eq!(drain_sorted(heap), [1, 0]);
+
+        // a forgotten guard leaks the rest of the heap instead of leaving it out of order
+        let mut heap: LeBinaryHeap<i32> = [1, 5, 3].into_iter().collect();
+        let mut top = heap.peek_mut().unwrap();
+        *top = 0;
+        mem::forget(top);
+        assert_eq!(heap.as_slice(), [0]);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut a: LeBinaryHeap<i32> = [1, 4, 7].into_iter().collect();
+        let mut b: LeBinaryHeap<i32> = (10..20).collect();
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(a.len(), 13);
+
+        let mut c: LeBinaryHeap<i32> = [2, 30].into_iter().collect();
+        a.append(&mut c);
+        let mut expected: Vec<i32> = (10..20).chain([1, 2, 4, 7, 30]).collect();
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(drain_sorted(a), expected);
+    }
+
+    #[test]
+    fn test_comparator() {
+        let mut heap = LeBinaryHeap::with_order(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+        heap.extend(["aa", "a", "aaaa", "aaa"]);
+        assert_eq!(heap.pop(), Some("aaaa"));
+        assert_eq!(heap.into_sorted_vec(), ["a", "aa", "aaa"]);
+    }
+
+    #[test]
+    fn test_panicking_order() {
+        let counter = Rc::new(());
+        let values: Vec<(i32, Rc<()>)> = (0..32).map(|i| ((i * 7) % 32, counter.clone())).collect();
+
+        // panic on every comparison in turn, the heap must keep every element exactly once
+        for panic_at in 1..100 {
+            let calls = Cell::new(0);
+            let order = |a: &(i32, Rc<()>), b: &(i32, Rc<()>)| {
+                calls.set(calls.get() + 1);
+                if calls.get() == panic_at {
+                    panic!("comparison panicked");
+                }
+                a.0.cmp(&b.0)
+            };
+
+            let mut heap = LeBinaryHeap::with_order(order);
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                for value in &values {
+                    heap.push(value.clone());
+                }
+                while heap.pop().is_some() {}
+            }));
+            if result.is_ok() {
+                break;
+            }
+
+            let mut left: Vec<i32> = heap.iter().map(|value| value.0).collect();
+            left.sort();
+            left.dedup();
+            assert_eq!(left.len(), heap.len(), "panic at {panic_at}");
+            drop(heap);
+            assert_eq!(Rc::strong_count(&counter), 33, "panic at {panic_at}");
+        }
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_panicking_pop() {
+        let armed = Cell::new(false);
+        let order = |a: &i32, b: &i32| {
+            if armed.get() {
+                panic!("comparison panicked");
+            }
+            a.cmp(b)
+        };
+
+        let mut heap = LeBinaryHeap::with_order(order);
+        heap.extend(0..8);
+        armed.set(true);
+        assert!(catch_unwind(AssertUnwindSafe(|| heap.pop())).is_err());
+        armed.set(false);
+
+        // the root that was being popped is still in the heap
+        assert_eq!(heap.len(), 8);
+        let mut left: Vec<i32> = heap.iter().copied().collect();
+        left.sort();
+        assert_eq!(left, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_append_keeps_order() {
+        type Order = fn(&i32, &i32) -> Ordering;
+        let mut min = LeBinaryHeap::with_order((|a, b| b.cmp(a)) as Order);
+        min.extend([5, 3]);
+        let mut max = LeBinaryHeap::with_order(Ord::cmp as Order);
+        max.extend([1, 9, 4, 7]);
+
+        // the bigger heap's elements move over, but the order stays min's
+        min.append(&mut max);
+        assert!(max.is_empty());
+        assert_eq!(min.into_sorted_vec(), [9, 7, 5, 4, 3, 1]);
+    }
+}
@@ -0,0 +1,139 @@
+use std::{iter::FusedIterator, marker::PhantomData, mem::ManuallyDrop, ptr};
+
+use crate::{raw::RawLeVec, Global, GrowthPolicy, LeAllocator, LeVec};
+
+/// An owning iterator over the elements of a [`LeVec`], yielded front to back.
+pub struct IntoIter<T, A: LeAllocator = Global> {
+    buf: RawLeVec<T, A>,
+    start: usize,
+    end: usize,
+    /// Tells drop check that the iterator owns and drops values of `T`.
+    _marker: PhantomData<T>,
+}
+
+impl<T, A: LeAllocator> IntoIter<T, A> {
+    pub(crate) fn new<G: GrowthPolicy>(vec: LeVec<T, A, G>) -> Self {
+        let vec = ManuallyDrop::new(vec);
+        Self {
+            //SAFETY: vec is never dropped, so the buffer is moved out exactly once
+            buf: unsafe { ptr::read(&vec.buf) },
+            start: 0,
+            end: vec.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the allocator the buffer is allocated from.
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        //SAFETY: start..end are initialized elements that were not yielded yet
+        unsafe { std::slice::from_raw_parts(self.buf.ptr().add(self.start), self.len()) }
+    }
+
+    /// Returns the remaining elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //SAFETY: start..end are initialized elements that were not yielded yet
+        unsafe { std::slice::from_raw_parts_mut(self.buf.ptr().add(self.start), self.len()) }
+    }
+}
+
+impl<T, A: LeAllocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            //SAFETY: start is less than end, so it points to an element that was not read yet
+            let value = unsafe { self.buf.ptr().add(self.start).read() };
+            self.start += 1;
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, A: LeAllocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            //SAFETY: end is greater or equal to start, so it points to an element that was not read yet
+            unsafe { Some(self.buf.ptr().add(self.end).read()) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, A: LeAllocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: LeAllocator> FusedIterator for IntoIter<T, A> {}
+
+impl<T, A: LeAllocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        //SAFETY: the remaining elements are initialized and will not be read again, the
+        //buffer is freed by RawLeVec even if one of them panics while dropping
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LeVec;
+
+    #[test]
+    fn test_into_iter_order() {
+        let mut vec = LeVec::new();
+        for i in 0..5 {
+            vec.push(i.to_string());
+        }
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().as_deref(), Some("0"));
+        assert_eq!(iter.next_back().as_deref(), Some("4"));
+        assert_eq!(iter.as_slice(), ["1", "2", "3"]);
+
+        iter.as_mut_slice()[0].push('!');
+        assert_eq!(iter.collect::<Vec<_>>(), ["1!", "2", "3"]);
+    }
+
+    #[test]
+    fn test_into_iter_drops_remaining() {
+        use std::rc::Rc;
+
+        let value = Rc::new(());
+        let mut vec = LeVec::new();
+        for _ in 0..6 {
+            vec.push(Rc::clone(&value));
+        }
+
+        let mut iter = vec.into_iter();
+        iter.next();
+        iter.next_back();
+        assert_eq!(Rc::strong_count(&value), 5);
+
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_into_iter_fused() {
+        let mut vec = LeVec::new();
+        vec.push(());
+
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next(), Some(()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+}
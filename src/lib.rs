@@ -1,19 +1,309 @@
-use std::{ops::Index, ptr};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr,
+    slice::SliceIndex,
+};
 
-const ISIZE_MAX_SIZE: usize = isize::MAX as usize;
+/// Implements `PartialEq` by comparing both sides as slices.
+macro_rules! impl_slice_eq {
+    ([$($vars:tt)*] $lhs:ty, $rhs:ty) => {
+        impl<T, U, $($vars)*> PartialEq<$rhs> for $lhs
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &$rhs) -> bool {
+                self[..] == other[..]
+            }
+        }
+    };
+}
+
+mod alloc;
+mod array;
+mod convert;
+mod deque;
+mod drain;
+mod error;
+mod extract_if;
+mod growth;
+mod heap;
+mod into_iter;
+mod macros;
+mod raw;
+mod shift;
+mod small;
+mod sorted;
+mod splice;
+#[cfg(test)]
+mod test_util;
+
+pub use alloc::{AllocError, Global, LeAllocator};
+pub use array::{ArrayDrain, ArrayIntoIter, ArrayLeVec};
+pub use deque::{DequeIntoIter, DequeIter, DequeIterMut, LeVecDeque};
+pub use drain::Drain;
+pub use error::{CapacityError, InsertError, OutOfBoundsError, TryPushError, TryReserveError};
+pub use extract_if::ExtractIf;
+pub use growth::{Doubling, FixedChunk, GrowthPolicy, OneAndAHalf, PageRounded};
+pub use heap::{HeapOrder, LeBinaryHeap, MaxHeap, MinHeap, PeekMut};
+pub use into_iter::IntoIter;
+#[doc(hidden)]
+pub use macros::__private;
+pub use small::{SmallIntoIter, SmallLeVec};
+pub use sorted::SortedLeVec;
+pub use splice::Splice;
+
+use raw::RawLeVec;
 
-pub struct LeVec<T> {
-    pub ptr: ptr::NonNull<T>,
-    pub len: usize,
-    pub cap: usize,
+/// Resolves `range` against a slice of length `len`, panicking if it is out of bounds.
+pub(crate) fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    start..end
+}
+
+/// A contiguous growable array, allocated from `A` and grown as `G` decides.
+///
+/// `LeVec` owns its elements: it is [`Send`] and [`Sync`] exactly when `T` (and the
+/// allocator) are, and it is covariant in `T` like [`Vec`].
+///
+/// ```
+/// use le_vec::LeVec;
+///
+/// fn assert_send_sync<T: Send + Sync>() {}
+/// assert_send_sync::<LeVec<String>>();
+///
+/// fn shorten<'a>(vec: LeVec<&'static str>) -> LeVec<&'a str> {
+///     vec
+/// }
+/// assert_eq!(shorten(LeVec::from(["a"])), ["a"]);
+/// ```
+///
+/// A vector of [`Rc`](std::rc::Rc)s cannot be sent to another thread:
+///
+/// ```compile_fail
+/// use le_vec::LeVec;
+/// use std::rc::Rc;
+///
+/// fn assert_send<T: Send>() {}
+/// assert_send::<LeVec<Rc<u8>>>();
+/// ```
+///
+/// and a vector of [`Cell`](std::cell::Cell)s cannot be shared between threads:
+///
+/// ```compile_fail
+/// use le_vec::LeVec;
+/// use std::cell::Cell;
+///
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<LeVec<Cell<u8>>>();
+/// ```
+///
+/// Unlike [`Vec`], the [`Drop`] impl cannot promise the compiler that it only drops the
+/// elements and never looks at what they borrow, since `#[may_dangle]` is unstable. So a
+/// `LeVec` holding references must be dropped before the values they point to:
+///
+/// ```compile_fail
+/// use le_vec::LeVec;
+///
+/// let mut vec = LeVec::new();
+/// let value = String::from("dropped first");
+/// vec.push(&value);
+/// ```
+pub struct LeVec<T, A: LeAllocator = Global, G: GrowthPolicy = Doubling> {
+    buf: RawLeVec<T, A>,
+    len: usize,
+    /// Tells drop check that the vector owns and drops values of `T`.
+    _marker: PhantomData<(T, G)>,
 }
 
+//SAFETY: LeVec owns its elements and allocator like a Box<[T], A> would, nothing else
+//points into the buffer
+unsafe impl<T: Send, A: LeAllocator + Send, G: GrowthPolicy> Send for LeVec<T, A, G> {}
+//SAFETY: shared access only hands out &T and &A
+unsafe impl<T: Sync, A: LeAllocator + Sync, G: GrowthPolicy> Sync for LeVec<T, A, G> {}
+
 impl<T> LeVec<T> {
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates an empty vector with room for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    /// Fallible version of [`with_capacity`](Self::with_capacity), never panics or aborts.
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    /// Creates a vector from a pointer, a length and a capacity.
+    ///
+    /// # Safety
+    ///
+    /// Same contract as [`from_raw_parts_in`](Self::from_raw_parts_in) with the [`Global`]
+    /// allocator.
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Self {
+        Self::from_raw_parts_in(ptr, length, capacity, Global)
+    }
+
+    /// Decomposes the vector into its pointer, length and capacity.
+    ///
+    /// The caller becomes responsible for the memory, which can be handed back with
+    /// [`from_raw_parts`](Self::from_raw_parts).
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let (ptr, len, cap, _) = self.into_raw_parts_with_alloc();
+        (ptr, len, cap)
+    }
+}
+
+impl<T, A: LeAllocator> LeVec<T, A> {
+    /// Creates an empty vector that will allocate from `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        Self::from_buf(RawLeVec::new_in(alloc), 0)
+    }
+
+    /// Creates an empty vector with room for at least `capacity` elements, allocated from `alloc`.
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::from_buf(RawLeVec::with_capacity_in(capacity, alloc), 0)
+    }
+
+    /// Fallible version of [`with_capacity_in`](Self::with_capacity_in), never panics or aborts.
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Self::from_buf(
+            RawLeVec::try_with_capacity_in(capacity, alloc)?,
+            0,
+        ))
+    }
+
+    /// Creates a vector from a pointer, a length, a capacity and an allocator.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must have been allocated by `alloc` with the layout of `[T; capacity]`, or
+    ///   be dangling and well aligned if `capacity` is 0 or `T` is zero-sized.
+    /// - `length` must not be greater than `capacity`.
+    /// - The first `length` elements must be initialized values of `T`.
+    /// - Ownership of the allocation is transferred, nothing else may use or free it.
+    pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize, alloc: A) -> Self {
+        Self::from_buf(RawLeVec::from_raw_parts_in(ptr, capacity, alloc), length)
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> LeVec<T, A, G> {
+    /// Wraps a buffer whose first `len` slots are initialized.
+    fn from_buf(buf: RawLeVec<T, A>, len: usize) -> Self {
         Self {
-            ptr: ptr::NonNull::dangling(),
-            len: 0,
-            cap: 0,
+            buf,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Switches the vector to another [`GrowthPolicy`], keeping its buffer.
+    pub fn with_growth<H: GrowthPolicy>(self) -> LeVec<T, A, H> {
+        let vec = ManuallyDrop::new(self);
+        //SAFETY: vec is never dropped, so the buffer is moved out exactly once
+        let buf = unsafe { ptr::read(&vec.buf) };
+        LeVec::from_buf(buf, vec.len)
+    }
+
+    /// Decomposes the vector into its pointer, length, capacity and allocator.
+    ///
+    /// The caller becomes responsible for the memory, which can be handed back with
+    /// [`from_raw_parts_in`](Self::from_raw_parts_in).
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        let vec = ManuallyDrop::new(self);
+        //SAFETY: vec is never dropped, so the buffer is moved out exactly once
+        let buf = unsafe { ptr::read(&vec.buf) };
+        let (ptr, cap, alloc) = buf.into_raw_parts();
+        (ptr, vec.len, cap, alloc)
+    }
+
+    /// Consumes the vector and leaks its buffer, returning the elements as a slice that
+    /// lives as long as the allocator.
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where
+        A: 'a,
+    {
+        let mut vec = ManuallyDrop::new(self);
+        //SAFETY: the buffer is never freed, so the elements stay valid for 'a
+        unsafe { std::slice::from_raw_parts_mut(vec.as_mut_ptr(), vec.len) }
+    }
+
+    /// Creates a vector of `len` elements whose bytes are all zero, allocated at once.
+    ///
+    /// # Safety
+    ///
+    /// A value of `T` made of zero bytes must be valid.
+    pub(crate) unsafe fn zeroed_in(len: usize, alloc: A) -> Self {
+        Self::from_buf(RawLeVec::with_capacity_zeroed_in(len, alloc), len)
+    }
+
+    /// Returns the allocator the buffer is allocated from.
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    /// Sets the length of the vector without dropping or initializing anything.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must not be greater than [`capacity`](Self::capacity).
+    /// - The elements at `old_len..new_len` must be initialized.
+    /// - The elements at `new_len..old_len` are forgotten, the caller takes care of them.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.len = new_len;
+    }
+
+    /// Returns the allocated but unused slots after the elements.
+    ///
+    /// Write into them and call [`set_len`](Self::set_len) to make them part of the vector.
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.split_at_spare_mut().1
+    }
+
+    /// Returns the elements and the spare capacity at the same time.
+    pub fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>]) {
+        let spare_len = self.capacity() - self.len;
+        //SAFETY: 0..len is initialized and len..capacity is allocated, the ranges are disjoint
+        unsafe {
+            let base = self.buf.ptr();
+            (
+                std::slice::from_raw_parts_mut(base, self.len),
+                std::slice::from_raw_parts_mut(
+                    base.add(self.len) as *mut MaybeUninit<T>,
+                    spare_len,
+                ),
+            )
         }
     }
 
@@ -21,143 +311,706 @@ impl<T> LeVec<T> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Zero-sized types never allocate, so their capacity is unbounded.
     pub fn capacity(&self) -> usize {
-        self.cap
+        self.buf.capacity()
     }
 
     pub fn push(&mut self, value: T) {
-        let size = std::mem::size_of::<T>();
-        //TODO: see what std does when size == 0
-        assert!(size > 0, "size of T must be greater than 0");
-        if self.len == 0 {
-            let new_size = size.checked_mul(4).expect("capacity overflow");
-            assert!(new_size <= ISIZE_MAX_SIZE, "capacity overflow");
-            let layout = std::alloc::Layout::array::<T>(4).unwrap();
-
-            //SAFETY: layout is size_of::<T>() * 4 and size_of::<T>() > 0
-            let ptr = unsafe { std::alloc::alloc(layout) as *mut T };
-
-            let ptr = ptr::NonNull::new(ptr).expect("allocation failed");
-
-            //SAFETY: ptr is non-null,the value is not read and the value is not dropped
-            unsafe { ptr.as_ptr().write(value) };
-            self.ptr = ptr;
-            self.len = 1;
-            self.cap = 4;
-        } else if self.len < self.cap {
-            let offset = self.len.checked_mul(size).expect("capacity overflow");
-            assert!(offset <= isize::MAX as usize, "capacity overflow");
-
-            //SAFETY: offset is less than capacity, offset fits in isize
-            unsafe {
-                self.ptr.as_ptr().add(self.len).write(value);
-            }
-            self.len += 1;
-        } else {
-            let new_cap = self.cap.checked_mul(2).expect("capacity overflow");
-            let new_size = size.checked_mul(new_cap).expect("capacity overflow");
-            assert!(new_size <= isize::MAX as usize, "capacity overflow");
-            let layout = std::alloc::Layout::array::<T>(new_cap).unwrap();
-
-            // Calculate the maximum size that can be represented by isize_max_size
-            // when rounded up to the nearest multiple of layout.align()
-            let aligned_isize_max_size = ISIZE_MAX_SIZE + (layout.align() - 1) as usize;
-            let aligned_isize_max_size_rounded =
-                aligned_isize_max_size - (aligned_isize_max_size % layout.align());
-
-            assert!(
-                new_size <= aligned_isize_max_size_rounded,
-                "capacity overflow"
+        if self.len == self.buf.capacity() {
+            self.buf.grow_one::<G>();
+        }
+
+        //SAFETY: len is less than capacity, so the slot is allocated and uninitialized
+        unsafe {
+            self.buf.ptr().add(self.len).write(value);
+        }
+        self.len += 1;
+    }
+
+    /// Fallible version of [`push`](Self::push), never panics or aborts.
+    ///
+    /// If the buffer could not grow, `value` is handed back in the [`TryPushError`].
+    pub fn try_push(&mut self, value: T) -> Result<(), TryPushError<T>> {
+        if let Err(error) = self.buf.try_reserve::<G>(self.len, 1) {
+            return Err(TryPushError { error, value });
+        }
+
+        //SAFETY: len is less than capacity, so the slot is allocated and uninitialized
+        unsafe {
+            self.buf.ptr().add(self.len).write(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Inserts `value` at `index`, shifting every element after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        if let Err(error) = self.try_insert(index, value) {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                error.index, error.len
             );
+        }
+    }
 
-            /*SAFETY:
-                ptr is non-null
-                ptr was allocated via this allocator
-                layout is the same layout that was used to allocate ptr
-                new_size when rounded up to the nearest multiple of layout.align() fits in isize
-            */
-            let ptr = unsafe {
-                std::alloc::realloc(self.ptr.as_ptr() as *mut u8, layout, new_size) as *mut T
-            };
-            let ptr = ptr::NonNull::new(ptr).expect("allocation failed");
-            unsafe {
-                ptr.as_ptr().add(self.len).write(value);
-            }
-            self.ptr = ptr;
+    /// Inserts `value` at `index`, giving it back inside the error if `index > len`.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), InsertError<T>> {
+        if index > self.len {
+            return Err(InsertError {
+                index,
+                len: self.len,
+                value,
+            });
+        }
+
+        if self.len == self.buf.capacity() {
+            self.buf.grow_one::<G>();
+        }
+
+        //SAFETY: index <= len < capacity, so both the shifted range and the slot are allocated
+        unsafe {
+            let slot = self.buf.ptr().add(index);
+            ptr::copy(slot, slot.add(1), self.len - index);
+            slot.write(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, shifting every element after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        match self.try_remove(index) {
+            Ok(value) => value,
+            Err(error) => panic!(
+                "removal index (is {}) should be < len (is {})",
+                error.index, error.len
+            ),
+        }
+    }
+
+    /// Removes and returns the element at `index`, or an error if `index >= len`.
+    pub fn try_remove(&mut self, index: usize) -> Result<T, OutOfBoundsError> {
+        if index >= self.len {
+            return Err(OutOfBoundsError {
+                index,
+                len: self.len,
+            });
+        }
+
+        //SAFETY: index < len, the element is read once and the hole is closed right after
+        unsafe {
+            let slot = self.buf.ptr().add(index);
+            let value = slot.read();
+            ptr::copy(slot.add(1), slot, self.len - index - 1);
+            self.len -= 1;
+            Ok(value)
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        assert!(
+            index < len,
+            "swap_remove index (is {index}) should be < len (is {len})"
+        );
+
+        //SAFETY: index and len - 1 are both in bounds, the removed element is read once
+        unsafe {
+            let base = self.buf.ptr();
+            let value = base.add(index).read();
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.len -= 1;
+            value
+        }
+    }
+
+    /// Fallible version of [`extend_from_slice`](Self::extend_from_slice), never panics or
+    /// aborts on allocation failure.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), TryReserveError>
+    where
+        T: Clone,
+    {
+        self.try_reserve(other.len())?;
+        self.extend_from_slice(other);
+        Ok(())
+    }
+
+    /// Clones and appends every element of `other`, allocating at most once.
+    ///
+    /// For `Copy` types [`extend_from_copy_slice`](Self::extend_from_copy_slice) copies the
+    /// whole slice with a single memcpy.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.buf.reserve::<G>(self.len, other.len());
+        for value in other {
+            //SAFETY: the space was reserved above, len is bumped right after each write so a
+            //panicking clone leaves every written element owned by the vector
+            unsafe { self.buf.ptr().add(self.len).write(value.clone()) };
+            self.len += 1;
+        }
+    }
+
+    /// Appends every element of `other` with a single memcpy.
+    pub fn extend_from_copy_slice(&mut self, other: &[T])
+    where
+        T: Copy,
+    {
+        let count = other.len();
+        self.buf.reserve::<G>(self.len, count);
+        //SAFETY: the space was reserved above and other cannot overlap the spare capacity
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.buf.ptr().add(self.len), count);
+        }
+        self.len += count;
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest. Does nothing if `len` is
+    /// not lower than the current length.
+    ///
+    /// The length is updated before anything is dropped, so a panicking destructor can
+    /// never make the vector drop the same element twice.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let remaining = self.len - len;
+        //SAFETY: len..self.len is initialized and is no longer visible once the length shrinks
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.buf.ptr().add(len), remaining);
+            self.len = len;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops every element, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Resizes the vector to `new_len`, filling new slots with clones of `value`.
+    ///
+    /// The last new slot gets `value` itself instead of a clone.
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+
+        let additional = new_len - self.len;
+        self.reserve(additional);
+        for _ in 1..additional {
+            //SAFETY: the space was reserved above, len is bumped right after each write so a
+            //panicking clone leaves every written element owned by the vector
+            unsafe { self.buf.ptr().add(self.len).write(value.clone()) };
             self.len += 1;
-            self.cap = new_cap;
         }
+        //SAFETY: one reserved slot is left for the original value
+        unsafe { self.buf.ptr().add(self.len).write(value) };
+        self.len += 1;
     }
 
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index < self.len {
-            //SAFETY: index is less than length
-            unsafe { Some(&*self.ptr.as_ptr().add(index)) }
+    /// Resizes the vector to `new_len`, filling new slots with the values returned by `f`.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, f: F) {
+        if new_len <= self.len {
+            self.truncate(new_len);
         } else {
-            None
+            let additional = new_len - self.len;
+            self.extend(std::iter::repeat_with(f).take(additional));
+        }
+    }
+
+    /// Splits the vector in two at `at`, returning the elements from `at` onwards in a new
+    /// vector allocated from a clone of the allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        let len = self.len;
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+
+        let other_len = len - at;
+        let mut other =
+            LeVec::with_capacity_in(other_len, self.buf.allocator().clone()).with_growth();
+        //SAFETY: at..len is initialized and moves to other, which has room for all of it
+        unsafe {
+            self.len = at;
+            ptr::copy_nonoverlapping(self.buf.ptr().add(at), other.buf.ptr(), other_len);
+            other.len = other_len;
         }
+        other
+    }
+
+    /// Moves every element of `other` to the end of this vector, leaving `other` empty.
+    pub fn append(&mut self, other: &mut Self) {
+        let count = other.len;
+        self.reserve(count);
+        //SAFETY: the space was reserved above, other gives up ownership by dropping its length
+        unsafe {
+            ptr::copy_nonoverlapping(other.buf.ptr(), self.buf.ptr().add(self.len), count);
+            other.len = 0;
+        }
+        self.len += count;
+    }
+
+    /// Removes `range` from the vector, yielding the removed elements in order.
+    ///
+    /// The elements after the range are moved back when the returned [`Drain`] is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A, G> {
+        let len = self.len;
+        let Range { start, end } = slice_range(range, len);
+
+        // only the prefix stays visible, a forgotten drain leaks the rest instead of double dropping it
+        self.len = start;
+
+        //SAFETY: start..end is in bounds and the elements stay initialized until the drain yields them
+        let drained = unsafe { std::slice::from_raw_parts(self.buf.ptr().add(start), end - start) };
+        Drain {
+            vec: ptr::NonNull::from(self),
+            iter: drained.iter(),
+            tail_start: end,
+            tail_len: len - end,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator that removes and yields the elements of `range` for which `pred`
+    /// returns `true`, compacting the rest in place.
+    ///
+    /// Elements are only removed as the iterator advances. If it is dropped early, the
+    /// elements that were not visited stay in the vector in their original order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn extract_if<R, F>(&mut self, range: R, pred: F) -> ExtractIf<'_, T, F, A, G>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len;
+        let Range { start, end } = slice_range(range, old_len);
+
+        // the buffer has holes while extracting, the iterator restores the length on drop
+        self.len = 0;
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            pred,
+        }
+    }
+
+    /// Replaces `range` with the items of `replace_with`, yielding the removed elements.
+    ///
+    /// The replacement is written when the returned [`Splice`] is dropped. The tail is moved
+    /// only once when the iterator's `size_hint` is exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, A, G>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|value| f(value));
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, passing each one mutably.
+    ///
+    /// If `f` or a destructor panics, the elements that were not visited yet are kept.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        //SAFETY: the buffer holds len initialized elements and self is mutably borrowed
+        unsafe { shift::retain_mut(self.buf.ptr(), &mut self.len, f) };
+    }
+
+    /// Removes consecutive elements that map to the same key.
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)` returns `true`.
+    ///
+    /// If `same_bucket` or a destructor panics, the elements that were not visited yet are kept.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        // moves the unvisited elements over the gap, even while unwinding
+        struct FillGapOnDrop<'a, T, A: LeAllocator, G: GrowthPolicy> {
+            vec: &'a mut LeVec<T, A, G>,
+            read: usize,
+            write: usize,
+        }
+
+        impl<T, A: LeAllocator, G: GrowthPolicy> Drop for FillGapOnDrop<'_, T, A, G> {
+            fn drop(&mut self) {
+                let remaining = self.vec.len - self.read;
+                //SAFETY: read..len is initialized and write <= read
+                unsafe {
+                    let base = self.vec.buf.ptr();
+                    ptr::copy(base.add(self.read), base.add(self.write), remaining);
+                }
+                self.vec.len = self.write + remaining;
+            }
+        }
+
+        let len = self.len;
+        if len <= 1 {
+            return;
+        }
+
+        let mut gap = FillGapOnDrop {
+            vec: self,
+            read: 1,
+            write: 1,
+        };
+
+        while gap.read < len {
+            //SAFETY: write - 1 < read < len, both elements are initialized and distinct
+            unsafe {
+                let base = gap.vec.buf.ptr();
+                let current = base.add(gap.read);
+                let previous = base.add(gap.write - 1);
+                if same_bucket(&mut *current, &mut *previous) {
+                    // counted before dropping so a panicking destructor is not run twice
+                    gap.read += 1;
+                    ptr::drop_in_place(current);
+                } else {
+                    ptr::copy(current, base.add(gap.write), 1);
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+        }
+
+        gap.vec.len = gap.write;
+        std::mem::forget(gap);
+    }
+
+    /// Reserves room for at least `additional` more elements, growing geometrically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve::<G>(self.len, additional);
+    }
+
+    /// Reserves room for exactly `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.buf.reserve_exact(self.len, additional);
+    }
+
+    /// Fallible version of [`reserve`](Self::reserve), never panics or aborts.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve::<G>(self.len, additional)
+    }
+
+    /// Fallible version of [`reserve_exact`](Self::reserve_exact), never panics or aborts.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve_exact(self.len, additional)
+    }
+
+    /// Shrinks the capacity as close to `len` as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    /// Shrinks the capacity to `max(len, min_capacity)`, doing nothing if it is already lower.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.buf.shrink_to(std::cmp::max(self.len, min_capacity));
+    }
+
+    pub fn get<I: SliceIndex<[T]>>(&self, index: I) -> Option<&I::Output> {
+        self.as_slice().get(index)
+    }
+
+    pub fn get_mut<I: SliceIndex<[T]>>(&mut self, index: I) -> Option<&mut I::Output> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    /// Returns a raw pointer to the buffer, dangling while nothing is allocated.
+    pub fn as_ptr(&self) -> *const T {
+        self.buf.ptr()
+    }
+
+    /// Returns a raw mutable pointer to the buffer, dangling while nothing is allocated.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.ptr()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        //SAFETY: ptr is non-null and aligned, even when dangling, and the first len elements are initialized
+        unsafe { std::slice::from_raw_parts(self.buf.ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //SAFETY: ptr is non-null and aligned, even when dangling, and the first len elements are initialized
+        unsafe { std::slice::from_raw_parts_mut(self.buf.ptr(), self.len) }
     }
 
     pub fn pop(&mut self) -> Option<T> {
         if self.len > 0 {
             self.len -= 1;
             //SAFETY: self.len is greater than 0
-            unsafe { Some(self.ptr.as_ptr().add(self.len).read()) }
+            unsafe { Some(self.buf.ptr().add(self.len).read()) }
         } else {
             None
         }
     }
 }
 
-impl<T> Index<usize> for LeVec<T> {
-    type Output = T;
+impl<T: PartialEq, A: LeAllocator, G: GrowthPolicy> LeVec<T, A, G> {
+    /// Removes consecutive repeated elements.
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).expect("index out of bounds")
+impl<T, G: GrowthPolicy> FromIterator<T> for LeVec<T, Global, G> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = LeVec::new().with_growth();
+        vec.extend(iter);
+        vec
     }
 }
 
-impl<T> Drop for LeVec<T> {
-    fn drop(&mut self) {
-        for i in 0..self.len {
-            //SAFETY: i is less than length
-            unsafe {
-                self.ptr.as_ptr().add(i).drop_in_place();
+impl<T, A: LeAllocator, G: GrowthPolicy> Extend<T> for LeVec<T, A, G> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.buf.reserve::<G>(self.len, lower);
+
+        while let Some(value) = iter.next() {
+            if self.len == self.capacity() {
+                // the hint was too low, grow again with whatever the iterator promises now
+                let (lower, _) = iter.size_hint();
+                self.buf.reserve::<G>(self.len, lower.saturating_add(1));
             }
+
+            //SAFETY: len is less than capacity, so the slot is allocated and uninitialized
+            unsafe { self.buf.ptr().add(self.len).write(value) };
+            self.len += 1;
         }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: LeAllocator, G: GrowthPolicy> Extend<&'a T> for LeVec<T, A, G> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, G: GrowthPolicy> Default for LeVec<T, Global, G> {
+    fn default() -> Self {
+        LeVec::new().with_growth()
+    }
+}
+
+impl<T: Clone, A: LeAllocator + Clone, G: GrowthPolicy> Clone for LeVec<T, A, G> {
+    /// Clones every element into a buffer allocated once.
+    ///
+    /// If a `T::clone` panics, the elements cloned so far are dropped.
+    fn clone(&self) -> Self {
+        let mut vec = LeVec::with_capacity_in(self.len, self.buf.allocator().clone()).with_growth();
+        vec.extend_from_slice(self);
+        vec
+    }
+}
+
+impl<T: fmt::Debug, A: LeAllocator, G: GrowthPolicy> fmt::Debug for LeVec<T, A, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Hash, A: LeAllocator, G: GrowthPolicy> Hash for LeVec<T, A, G> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T: Eq, A: LeAllocator, G: GrowthPolicy> Eq for LeVec<T, A, G> {}
+
+impl<T, A1, A2, G1, G2> PartialOrd<LeVec<T, A2, G2>> for LeVec<T, A1, G1>
+where
+    T: PartialOrd,
+    A1: LeAllocator,
+    A2: LeAllocator,
+    G1: GrowthPolicy,
+    G2: GrowthPolicy,
+{
+    fn partial_cmp(&self, other: &LeVec<T, A2, G2>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
 
-        let layout = std::alloc::Layout::array::<T>(self.cap).unwrap();
-        /*
-           SAFETY:
-               ptr is non-null
-               ptr was allocated via this allocator
-               layout is the same layout that was used to allocate ptr
-        */
-        unsafe { std::alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout) };
+impl<T: Ord, A: LeAllocator, G: GrowthPolicy> Ord for LeVec<T, A, G> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
     }
 }
 
-impl<T> Iterator for LeVec<T> {
+impl_slice_eq! { [A1: LeAllocator, A2: LeAllocator, G1: GrowthPolicy, G2: GrowthPolicy] LeVec<T, A1, G1>, LeVec<U, A2, G2> }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] LeVec<T, A, G>, [U] }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] LeVec<T, A, G>, &[U] }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] LeVec<T, A, G>, &mut [U] }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy, const N: usize] LeVec<T, A, G>, [U; N] }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy, const N: usize] LeVec<T, A, G>, &[U; N] }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] LeVec<T, A, G>, Vec<U> }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] [T], LeVec<U, A, G> }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] &[T], LeVec<U, A, G> }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] &mut [T], LeVec<U, A, G> }
+impl_slice_eq! { [A: LeAllocator, G: GrowthPolicy] Vec<T>, LeVec<U, A, G> }
+
+impl<T, A: LeAllocator, G: GrowthPolicy> AsRef<[T]> for LeVec<T, A, G> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> AsMut<[T]> for LeVec<T, A, G> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> AsRef<LeVec<T, A, G>> for LeVec<T, A, G> {
+    fn as_ref(&self) -> &LeVec<T, A, G> {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> AsMut<LeVec<T, A, G>> for LeVec<T, A, G> {
+    fn as_mut(&mut self) -> &mut LeVec<T, A, G> {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Borrow<[T]> for LeVec<T, A, G> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> BorrowMut<[T]> for LeVec<T, A, G> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Deref for LeVec<T, A, G> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> DerefMut for LeVec<T, A, G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, A: LeAllocator, G: GrowthPolicy> Index<I> for LeVec<T, A, G> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, A: LeAllocator, G: GrowthPolicy> IndexMut<I> for LeVec<T, A, G> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Drop for LeVec<T, A, G> {
+    fn drop(&mut self) {
+        //SAFETY: the first len elements are initialized, the buffer is freed by RawLeVec
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> IntoIterator for LeVec<T, A, G> {
     type Item = T;
+    type IntoIter = IntoIter<T, A>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.len > 0 {
-            self.len -= 1;
-            //SAFETY: self.len is greater than 0
-            unsafe { Some(self.ptr.as_ptr().add(self.len).read()) }
-        } else {
-            None
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
     }
 }
 
-impl<'a, T> IntoIterator for &'a LeVec<T> {
+impl<'a, T, A: LeAllocator, G: GrowthPolicy> IntoIterator for &'a LeVec<T, A, G> {
     type Item = &'a T;
     type IntoIter = std::slice::Iter<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        //SAFETY: self.ptr is non-null
-        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len).iter() }
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, A: LeAllocator, G: GrowthPolicy> IntoIterator for &'a mut LeVec<T, A, G> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
     }
 }
 #[cfg(test)]
@@ -202,4 +1055,520 @@ mod test {
             println!("iter {:?}", value);
         }
     }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut vec = LeVec::new();
+        vec.push(());
+        vec.push(());
+        vec.push(());
+
+        assert_eq!(vec.len(), 3);
+        assert_eq!(vec.capacity(), usize::MAX);
+        assert_eq!(vec.get(2), Some(&()));
+        assert_eq!(vec.get(3), None);
+        assert_eq!(vec[0], ());
+        assert_eq!((&vec).into_iter().count(), 3);
+
+        assert_eq!(vec.pop(), Some(()));
+        assert_eq!(vec.len(), 2);
+    }
+
+    #[test]
+    fn test_zero_sized_drop() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        struct Marker;
+
+        impl Drop for Marker {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+            }
+        }
+
+        let mut vec = LeVec::new();
+        for _ in 0..5 {
+            vec.push(Marker);
+        }
+        drop(vec.pop());
+        assert_eq!(DROPS.with(|d| d.get()), 1);
+
+        drop(vec);
+        assert_eq!(DROPS.with(|d| d.get()), 5);
+    }
+
+    #[test]
+    fn test_slice_access() {
+        let mut vec = LeVec::new();
+        for value in [5, 3, 8, 1, 9, 2] {
+            vec.push(value);
+        }
+
+        vec.sort();
+        assert_eq!(&vec[..], [1, 2, 3, 5, 8, 9]);
+        assert_eq!(vec[1..3], [2, 3]);
+        assert_eq!(vec[..=1], [1, 2]);
+        assert_eq!(vec[4..], [8, 9]);
+        assert_eq!(vec.get(2..10), None);
+        assert!(vec.contains(&8));
+        assert_eq!(vec.binary_search(&5), Ok(3));
+
+        vec[0] = 10;
+        *vec.get_mut(1).unwrap() += 10;
+        for value in &mut vec {
+            *value *= 2;
+        }
+        assert_eq!(vec.as_slice(), [20, 24, 6, 10, 16, 18]);
+        assert_eq!(vec.chunks(4).count(), 2);
+    }
+
+    #[test]
+    fn test_empty_slice() {
+        let mut vec: LeVec<String> = LeVec::new();
+        assert!(vec.as_slice().is_empty());
+        assert!(vec.as_mut_slice().is_empty());
+        assert_eq!(vec.iter().count(), 0);
+        assert_eq!(vec.get(0), None);
+        assert!(vec[..].is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let mut vec = LeVec::new();
+        vec.push(1);
+        vec[1] = 2;
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut vec = LeVec::new();
+        for value in ["b", "d", "f"] {
+            vec.push(value.to_string());
+        }
+
+        vec.insert(0, "a".to_string());
+        vec.insert(2, "c".to_string());
+        vec.insert(5, "g".to_string());
+        vec.insert(4, "e".to_string());
+        assert_eq!(vec.as_slice(), ["a", "b", "c", "d", "e", "f", "g"]);
+        assert_eq!(vec.capacity(), 8);
+
+        assert_eq!(vec.remove(0), "a");
+        assert_eq!(vec.remove(5), "g");
+        assert_eq!(vec.swap_remove(1), "c");
+        assert_eq!(vec.as_slice(), ["b", "f", "d", "e"]);
+        assert_eq!(vec.swap_remove(3), "e");
+        assert_eq!(vec.as_slice(), ["b", "f", "d"]);
+    }
+
+    #[test]
+    fn test_try_insert_remove() {
+        let mut vec = LeVec::new();
+        vec.push(1);
+
+        let error = vec.try_insert(2, 7).unwrap_err();
+        assert_eq!((error.index, error.len), (2, 1));
+        assert_eq!(error.into_value(), 7);
+        assert_eq!(vec.try_insert(1, 2), Ok(()));
+
+        assert_eq!(
+            vec.try_remove(2),
+            Err(OutOfBoundsError { index: 2, len: 2 })
+        );
+        assert_eq!(vec.try_remove(0), Ok(1));
+        assert_eq!(vec.as_slice(), [2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 3) should be < len (is 3)")]
+    fn test_remove_out_of_bounds() {
+        let mut vec = LeVec::new();
+        vec.push(1);
+        vec.push(2);
+        vec.push(3);
+        vec.remove(3);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut vec = LeVec::new();
+        for value in 0..10 {
+            vec.push(value.to_string());
+        }
+
+        vec.retain(|value| value.parse::<i32>().unwrap() % 3 != 0);
+        assert_eq!(vec.as_slice(), ["1", "2", "4", "5", "7", "8"]);
+
+        vec.retain_mut(|value| {
+            value.push('!');
+            value.len() < 3
+        });
+        assert_eq!(vec.as_slice(), ["1!", "2!", "4!", "5!", "7!", "8!"]);
+    }
+
+    #[test]
+    fn test_retain_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut vec = LeVec::new();
+        for value in 0..6 {
+            vec.push((value, Rc::clone(&counter)));
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            vec.retain(|(value, _)| {
+                assert_ne!(*value, 3);
+                value % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+
+        let values: Vec<_> = vec.iter().map(|(value, _)| *value).collect();
+        assert_eq!(values, [0, 2, 3, 4, 5]);
+        assert_eq!(Rc::strong_count(&counter), 6);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut vec = LeVec::new();
+        for value in [1, 1, 2, 3, 3, 3, 1, 4, 4] {
+            vec.push(value);
+        }
+        vec.dedup();
+        assert_eq!(vec.as_slice(), [1, 2, 3, 1, 4]);
+
+        vec.dedup_by_key(|value| *value / 2);
+        assert_eq!(vec.as_slice(), [1, 2, 1, 4]);
+
+        let mut vec = LeVec::new();
+        for value in ["a", "A", "b", "B", "b", "c"] {
+            vec.push(value.to_string());
+        }
+        vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(vec.as_slice(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_dedup_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut vec = LeVec::new();
+        for value in [1, 1, 2, 2, 3, 3] {
+            vec.push((value, Rc::clone(&counter)));
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            vec.dedup_by(|(a, _), (b, _)| {
+                assert_ne!(*a, 3);
+                a == b
+            })
+        }));
+        assert!(result.is_err());
+
+        let values: Vec<_> = vec.iter().map(|(value, _)| *value).collect();
+        assert_eq!(values, [1, 2, 3, 3]);
+        assert_eq!(Rc::strong_count(&counter), 5);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let vec: LeVec<_> = (0..10).map(|value| value * 2).collect();
+        assert_eq!(vec.len(), 10);
+        assert_eq!(vec.capacity(), 10);
+        assert_eq!(vec[9], 18);
+
+        // filter has no useful lower bound, so growth falls back to doubling
+        let vec: LeVec<_> = (0..10).filter(|value| value % 2 == 0).collect();
+        assert_eq!(vec.as_slice(), [0, 2, 4, 6, 8]);
+        assert_eq!(vec.capacity(), 8);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut vec: LeVec<String> = ["a", "b"].iter().map(|value| value.to_string()).collect();
+        vec.extend(["c".to_string(), "d".to_string()]);
+        vec.extend_from_slice(&["e".to_string()]);
+        assert_eq!(vec.as_slice(), ["a", "b", "c", "d", "e"]);
+
+        let mut vec = LeVec::new();
+        vec.push(1u8);
+        vec.extend(&[2, 3]);
+        vec.extend_from_copy_slice(&[4, 5, 6, 7]);
+        assert_eq!(vec.as_slice(), [1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_capacity_management() {
+        let mut vec = LeVec::with_capacity(10);
+        assert_eq!(vec.capacity(), 10);
+        vec.extend(0..10);
+        assert_eq!(vec.capacity(), 10);
+
+        vec.reserve(1);
+        assert_eq!(vec.capacity(), 20);
+        vec.reserve_exact(15);
+        assert_eq!(vec.capacity(), 25);
+        vec.reserve(5);
+        assert_eq!(vec.capacity(), 25);
+
+        vec.shrink_to(12);
+        assert_eq!(vec.capacity(), 12);
+        vec.shrink_to(30);
+        assert_eq!(vec.capacity(), 12);
+        vec.shrink_to_fit();
+        assert_eq!(vec.capacity(), 10);
+        assert_eq!(vec.as_slice(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        vec.drain(..);
+        vec.shrink_to_fit();
+        assert_eq!(vec.capacity(), 0);
+        assert_eq!(vec.as_ptr(), ptr::NonNull::dangling().as_ptr());
+
+        vec.push(1);
+        assert_eq!(vec.capacity(), 4);
+    }
+
+    #[test]
+    fn test_capacity_zero_sized() {
+        let mut vec: LeVec<()> = LeVec::with_capacity(10);
+        vec.reserve_exact(100);
+        vec.shrink_to_fit();
+        assert_eq!(vec.capacity(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_reserve_overflow() {
+        let mut vec: LeVec<u64> = LeVec::new();
+        vec.reserve(usize::MAX / 4);
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        let mut vec: LeVec<u64> = LeVec::try_with_capacity(3).unwrap();
+        assert_eq!(vec.capacity(), 3);
+        vec.try_push(1).unwrap();
+        vec.try_extend_from_slice(&[2, 3, 4]).unwrap();
+        assert_eq!(vec.as_slice(), [1, 2, 3, 4]);
+
+        assert_eq!(
+            vec.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            vec.try_reserve_exact(usize::MAX / 8),
+            Err(TryReserveError::CapacityOverflow)
+        );
+
+        // a failed reservation leaves the buffer untouched
+        assert_eq!(vec.as_slice(), [1, 2, 3, 4]);
+        assert!(matches!(
+            LeVec::<u64>::try_with_capacity(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri aborts on the huge allocation instead of failing it
+    fn test_try_reserve_alloc_error() {
+        let mut vec: LeVec<u64> = [1, 2, 3, 4].into_iter().collect();
+
+        // fits in isize but no allocator can hand it out
+        let huge = isize::MAX as usize / 8 - 4;
+        match vec.try_reserve_exact(huge) {
+            Err(TryReserveError::AllocError { layout }) => {
+                assert_eq!(layout, std::alloc::Layout::array::<u64>(huge + 4).unwrap())
+            }
+            other => panic!("expected an allocation error, got {other:?}"),
+        }
+        assert_eq!(vec.as_slice(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_try_reserve_zero_sized() {
+        let mut vec = LeVec::new();
+        vec.try_push(()).unwrap();
+        assert_eq!(vec.try_reserve(10), Ok(()));
+        assert_eq!(
+            vec.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn test_std_traits() {
+        use std::collections::HashMap;
+
+        let vec: LeVec<_> = ["a", "b"].iter().map(|value| value.to_string()).collect();
+        let copy = vec.clone();
+        assert_eq!(vec, copy);
+        assert_eq!(format!("{vec:?}"), r#"["a", "b"]"#);
+        assert_eq!(LeVec::<i32>::default(), []);
+
+        let mut map = HashMap::new();
+        map.insert(copy, 1);
+        assert_eq!(
+            map.get(["a".to_string(), "b".to_string()].as_slice()),
+            Some(&1)
+        );
+
+        let numbers: LeVec<i32> = (1..4).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+        assert_eq!(numbers, &[1, 2, 3]);
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(numbers, [1, 2, 3][..]);
+        assert_eq!(vec![1, 2, 3], numbers);
+        assert_eq!(&[1, 2, 3][..], numbers);
+        assert_ne!(numbers, [1, 2]);
+
+        let bigger: LeVec<i32> = (1..5).collect();
+        assert!(numbers < bigger);
+        assert_eq!(numbers.cmp(&bigger), Ordering::Less);
+        assert_eq!(numbers.as_ref() as &[i32], [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_clone_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        struct PanicOnClone(usize, Rc<()>);
+
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                assert_ne!(self.0, 3);
+                PanicOnClone(self.0, Rc::clone(&self.1))
+            }
+        }
+
+        let counter = Rc::new(());
+        let vec: LeVec<_> = (0..5)
+            .map(|i| PanicOnClone(i, Rc::clone(&counter)))
+            .collect();
+
+        let result = catch_unwind(AssertUnwindSafe(|| vec.clone()));
+        assert!(result.is_err());
+        // the three clones that made it were dropped again
+        assert_eq!(Rc::strong_count(&counter), 6);
+        assert_eq!(vec.iter().map(|value| value.0).sum::<usize>(), 10);
+    }
+
+    #[test]
+    fn test_length_editing() {
+        let mut vec: LeVec<String> = LeVec::new();
+        vec.resize(3, "a".to_string());
+        assert_eq!(vec, ["a", "a", "a"]);
+
+        let mut next = 0;
+        vec.resize_with(5, || {
+            next += 1;
+            next.to_string()
+        });
+        assert_eq!(vec, ["a", "a", "a", "1", "2"]);
+
+        let mut tail = vec.split_off(3);
+        assert_eq!(vec, ["a", "a", "a"]);
+        assert_eq!(tail, ["1", "2"]);
+
+        vec.truncate(10);
+        vec.truncate(1);
+        vec.append(&mut tail);
+        assert_eq!(vec, ["a", "1", "2"]);
+        assert!(tail.is_empty());
+
+        vec.resize(2, String::new());
+        assert_eq!(vec, ["a", "1"]);
+
+        let capacity = vec.capacity();
+        vec.clear();
+        assert!(vec.is_empty());
+        assert_eq!(vec.capacity(), capacity);
+        assert!(vec.split_off(0).is_empty());
+    }
+
+    #[test]
+    fn test_truncate_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        use crate::test_util::PanicOnDrop;
+
+        let counter = Rc::new(());
+        let mut vec: LeVec<_> = (0..5)
+            .map(|i| PanicOnDrop {
+                panics: i == 2,
+                _counter: Rc::clone(&counter),
+            })
+            .collect();
+
+        let result = catch_unwind(AssertUnwindSafe(|| vec.truncate(1)));
+        assert!(result.is_err());
+        assert_eq!(vec.len(), 1);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        drop(vec);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "`at` split index (is 2) should be <= len (is 1)")]
+    fn test_split_off_out_of_bounds() {
+        let mut vec = LeVec::new();
+        vec.push(1);
+        vec.split_off(2);
+    }
+
+    #[test]
+    fn test_raw_parts() {
+        let vec: LeVec<String> = ["a", "b"].iter().map(|value| value.to_string()).collect();
+        let (ptr, len, cap) = vec.into_raw_parts();
+        assert_eq!((len, cap), (2, 4));
+
+        //SAFETY: the parts come straight from into_raw_parts
+        let mut vec = unsafe { LeVec::from_raw_parts(ptr, len, cap) };
+        vec.push("c".to_string());
+        assert_eq!(vec, ["a", "b", "c"]);
+
+        let (_, len, cap) = LeVec::<()>::new().into_raw_parts();
+        assert_eq!((len, cap), (0, usize::MAX));
+    }
+
+    #[test]
+    fn test_spare_capacity() {
+        let mut vec = LeVec::with_capacity(8);
+        vec.push(1u8);
+
+        let spare = vec.spare_capacity_mut();
+        assert_eq!(spare.len(), 7);
+        for (i, slot) in spare.iter_mut().take(3).enumerate() {
+            slot.write(i as u8 + 2);
+        }
+        //SAFETY: the three slots after the first element were just written
+        unsafe { vec.set_len(4) };
+        assert_eq!(vec, [1, 2, 3, 4]);
+
+        let (init, spare) = vec.split_at_spare_mut();
+        init[0] = 0;
+        spare[0].write(5);
+        //SAFETY: the slot after the last element was just written
+        unsafe { vec.set_len(5) };
+        assert_eq!(vec, [0, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_leak() {
+        let vec: LeVec<i32> = (0..3).collect();
+        let slice: &'static mut [i32] = vec.leak();
+        slice[0] = 10;
+        assert_eq!(slice, [10, 1, 2]);
+    }
 }
@@ -0,0 +1,192 @@
+/// Creates a [`LeVec`](crate::LeVec) holding the arguments, like `vec!`.
+///
+/// - `le_vec![a, b, c]` allocates exactly once, with room for the listed elements.
+/// - `le_vec![elem; n]` clones `elem` `n - 1` times and moves it into the last slot.
+///   Zero integers, floats, `bool`s and `char`s skip the clones and get a zeroed
+///   allocation instead.
+///
+/// ```
+/// use le_vec::le_vec;
+///
+/// let vec = le_vec![1, 2, 3];
+/// assert_eq!(vec, [1, 2, 3]);
+///
+/// let vec = le_vec![String::from("a"); 2];
+/// assert_eq!(vec, ["a", "a"]);
+/// ```
+#[macro_export]
+macro_rules! le_vec {
+    () => {
+        $crate::LeVec::new()
+    };
+    ($elem:expr; $n:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{CloneKind as _, ZeroedKind as _};
+        let elem = $elem;
+        (&&$crate::__private::Kind::of(&elem)).kind().repeat(elem, $n)
+    }};
+    ($($x:expr),+ $(,)?) => {
+        $crate::LeVec::from([$($x),+])
+    };
+}
+
+/// Support for `le_vec![elem; n]`, not part of the public API.
+///
+/// `(&&Kind::of(&elem)).kind()` resolves to [`Zeroed`](__private::Zeroed) when
+/// `T: IsZero` and falls back to [`Cloning`](__private::Cloning) one autoref level down
+/// otherwise, which is how the macro picks a strategy without specialization.
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    use crate::LeVec;
+
+    /// Types for which a value made of zero bytes is valid.
+    ///
+    /// # Safety
+    ///
+    /// `is_zero` may only return `true` if every byte of the value is zero.
+    pub unsafe trait IsZero {
+        fn is_zero(&self) -> bool;
+    }
+
+    macro_rules! impl_is_zero {
+        ($($t:ty => $is_zero:expr),* $(,)?) => {
+            $(
+                unsafe impl IsZero for $t {
+                    #[inline]
+                    fn is_zero(&self) -> bool {
+                        let is_zero: fn(&$t) -> bool = $is_zero;
+                        is_zero(self)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_is_zero! {
+        u8 => |x| *x == 0,
+        u16 => |x| *x == 0,
+        u32 => |x| *x == 0,
+        u64 => |x| *x == 0,
+        u128 => |x| *x == 0,
+        usize => |x| *x == 0,
+        i8 => |x| *x == 0,
+        i16 => |x| *x == 0,
+        i32 => |x| *x == 0,
+        i64 => |x| *x == 0,
+        i128 => |x| *x == 0,
+        isize => |x| *x == 0,
+        // -0.0 is not all zero bytes, so floats compare their bits
+        f32 => |x| x.to_bits() == 0,
+        f64 => |x| x.to_bits() == 0,
+        bool => |x| !*x,
+        char => |x| *x == '\0',
+    }
+
+    pub struct Kind<T>(PhantomData<T>);
+
+    impl<T> Kind<T> {
+        pub fn of(_: &T) -> Self {
+            Kind(PhantomData)
+        }
+    }
+
+    pub trait ZeroedKind {
+        fn kind(self) -> Zeroed;
+    }
+
+    impl<T: IsZero + Clone> ZeroedKind for &&Kind<T> {
+        fn kind(self) -> Zeroed {
+            Zeroed
+        }
+    }
+
+    pub trait CloneKind {
+        fn kind(self) -> Cloning;
+    }
+
+    impl<T: Clone> CloneKind for &Kind<T> {
+        fn kind(self) -> Cloning {
+            Cloning
+        }
+    }
+
+    pub struct Zeroed;
+
+    impl Zeroed {
+        pub fn repeat<T: IsZero + Clone>(self, elem: T, n: usize) -> LeVec<T> {
+            if elem.is_zero() {
+                //SAFETY: IsZero guarantees that zero bytes are a valid T
+                unsafe { LeVec::zeroed_in(n, crate::Global) }
+            } else {
+                Cloning.repeat(elem, n)
+            }
+        }
+    }
+
+    pub struct Cloning;
+
+    impl Cloning {
+        pub fn repeat<T: Clone>(self, elem: T, n: usize) -> LeVec<T> {
+            let mut vec = LeVec::with_capacity(n);
+            vec.resize(n, elem);
+            vec
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    #[test]
+    fn test_list() {
+        let vec = le_vec!["a".to_string(), "b".to_string(),];
+        assert_eq!(vec, ["a", "b"]);
+        assert_eq!(vec.capacity(), 2);
+
+        let empty: crate::LeVec<i32> = le_vec![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_repeat() {
+        assert_eq!(le_vec![0u64; 5], [0; 5]);
+        assert_eq!(le_vec![7i32; 3], [7, 7, 7]);
+        assert_eq!(le_vec![-0.0f32; 2], [-0.0, -0.0]);
+        assert_eq!(le_vec![false; 2], [false, false]);
+        assert_eq!(le_vec![(); 4].len(), 4);
+        assert!(le_vec![0u8; 0].is_empty());
+
+        let vec = le_vec![0u16; 1000];
+        assert_eq!(vec.capacity(), 1000);
+        assert!(vec.iter().all(|&value| value == 0));
+    }
+
+    #[test]
+    // the double borrow is what the macro dispatches on
+    #[allow(clippy::needless_borrow)]
+    fn test_repeat_strategy() {
+        use super::__private::{CloneKind as _, Cloning, Kind, Zeroed, ZeroedKind as _};
+
+        let _: Zeroed = (&&Kind::of(&0u16)).kind();
+        let _: Zeroed = (&&Kind::of(&0.0f64)).kind();
+        let _: Cloning = (&&Kind::of(&String::new())).kind();
+        let _: Cloning = (&&Kind::of(&Some(0u8))).kind();
+    }
+
+    #[test]
+    fn test_repeat_clone() {
+        let value = Rc::new(());
+        // two clones plus the original moved into the last slot
+        let vec = le_vec![Rc::clone(&value); 3];
+        assert_eq!(Rc::strong_count(&value), 4);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let vec = le_vec![Rc::clone(&value); 0];
+        assert!(vec.is_empty());
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
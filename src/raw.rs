@@ -0,0 +1,356 @@
+use std::{alloc::Layout, mem::ManuallyDrop, ptr};
+
+use crate::{Global, GrowthPolicy, LeAllocator, TryReserveError};
+
+const ISIZE_MAX_SIZE: usize = isize::MAX as usize;
+
+/// Turns a failed reservation into the panic that the infallible methods promise.
+pub(crate) fn handle_reserve<V>(result: Result<V, TryReserveError>) -> V {
+    match result {
+        Ok(value) => value,
+        Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveError::AllocError { .. }) => panic!("allocation failed"),
+    }
+}
+
+/// The buffer behind [`LeVec`](crate::LeVec) and the other containers of this crate.
+///
+/// Owns the allocation of `[T; cap]` and nothing else: it never reads, writes or drops
+/// elements, so its users keep track of which slots are initialized. Zero-sized types are
+/// never allocated and report a capacity of `usize::MAX`.
+pub(crate) struct RawLeVec<T, A: LeAllocator = Global> {
+    ptr: ptr::NonNull<T>,
+    cap: usize,
+    alloc: A,
+}
+
+//SAFETY: the buffer is uniquely owned, so it can move to another thread along with its `T`s
+unsafe impl<T: Send, A: LeAllocator + Send> Send for RawLeVec<T, A> {}
+//SAFETY: shared access never touches the buffer without going through its owner
+unsafe impl<T: Sync, A: LeAllocator + Sync> Sync for RawLeVec<T, A> {}
+
+impl<T, A: LeAllocator> RawLeVec<T, A> {
+    pub(crate) const fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: ptr::NonNull::dangling(),
+            cap: 0,
+            alloc,
+        }
+    }
+
+    /// Allocates room for exactly `capacity` elements.
+    pub(crate) fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        let mut buf = Self::new_in(alloc);
+        handle_reserve(buf.try_reserve_exact(0, capacity));
+        buf
+    }
+
+    pub(crate) fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let mut buf = Self::new_in(alloc);
+        buf.try_reserve_exact(0, capacity)?;
+        Ok(buf)
+    }
+
+    /// Allocates room for exactly `capacity` elements, with every byte set to zero.
+    pub(crate) fn with_capacity_zeroed_in(capacity: usize, alloc: A) -> Self {
+        let mut buf = Self::new_in(alloc);
+        if std::mem::size_of::<T>() == 0 || capacity == 0 {
+            return buf;
+        }
+
+        let layout = handle_reserve(Self::layout_for(capacity));
+        let ptr = buf.alloc.allocate_zeroed(layout);
+        buf.ptr = handle_reserve(ptr.map_err(|_| TryReserveError::AllocError { layout })).cast();
+        buf.cap = capacity;
+        buf
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by `alloc` with the layout of `[T; capacity]`, or be
+    /// dangling and well aligned if `capacity` is 0 or `T` is zero-sized.
+    pub(crate) unsafe fn from_raw_parts_in(ptr: *mut T, capacity: usize, alloc: A) -> Self {
+        Self {
+            ptr: ptr::NonNull::new_unchecked(ptr),
+            // zero-sized types never own an allocation
+            cap: if std::mem::size_of::<T>() == 0 {
+                0
+            } else {
+                capacity
+            },
+            alloc,
+        }
+    }
+
+    /// Gives up ownership of the allocation without freeing it.
+    pub(crate) fn into_raw_parts(self) -> (*mut T, usize, A) {
+        let buf = ManuallyDrop::new(self);
+        //SAFETY: buf is never dropped, so the allocator is moved out exactly once
+        let alloc = unsafe { ptr::read(&buf.alloc) };
+        (buf.ptr(), buf.capacity(), alloc)
+    }
+
+    /// Returns the start of the buffer, dangling while nothing is allocated.
+    pub(crate) fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// Zero-sized types never allocate, so their capacity is unbounded.
+    pub(crate) fn capacity(&self) -> usize {
+        if std::mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            self.cap
+        }
+    }
+
+    pub(crate) fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Makes sure the first `len + additional` slots are allocated, growing as `G` decides.
+    pub(crate) fn reserve<G: GrowthPolicy>(&mut self, len: usize, additional: usize) {
+        handle_reserve(self.try_reserve::<G>(len, additional));
+    }
+
+    pub(crate) fn try_reserve<G: GrowthPolicy>(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        if self.needs_to_grow(len, additional) {
+            self.try_grow_amortized::<G>(len, additional)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Makes room for one more element after `cap`, for callers that found the buffer full.
+    pub(crate) fn grow_one<G: GrowthPolicy>(&mut self) {
+        handle_reserve(self.try_grow_amortized::<G>(self.capacity(), 1));
+    }
+
+    /// Makes sure the first `len + additional` slots are allocated, growing to exactly that.
+    pub(crate) fn reserve_exact(&mut self, len: usize, additional: usize) {
+        handle_reserve(self.try_reserve_exact(len, additional));
+    }
+
+    pub(crate) fn try_reserve_exact(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        if self.needs_to_grow(len, additional) {
+            let new_cap = len
+                .checked_add(additional)
+                .ok_or(TryReserveError::CapacityOverflow)?;
+            self.try_reallocate(new_cap)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Shrinks the allocation to exactly `cap` elements, freeing it if `cap` is 0.
+    ///
+    /// Does nothing if the capacity is already not greater than `cap`.
+    pub(crate) fn shrink_to(&mut self, cap: usize) {
+        // zero-sized types keep cap at 0, so they never get past this
+        if self.cap > cap {
+            handle_reserve(self.try_reallocate(cap));
+        }
+    }
+
+    fn needs_to_grow(&self, len: usize, additional: usize) -> bool {
+        self.capacity().wrapping_sub(len) < additional
+    }
+
+    /// Grows the buffer to hold at least `len + additional` elements, to the capacity `G`
+    /// picks.
+    fn try_grow_amortized<G: GrowthPolicy>(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let size = std::mem::size_of::<T>();
+        // zero-sized types are never allocated, so only their length can run out
+        if size == 0 {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        let required = len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let max_cap = ISIZE_MAX_SIZE / size;
+        let new_cap = G::next_capacity(self.cap, required, size)
+            .min(max_cap)
+            .max(required);
+        self.try_reallocate(new_cap)
+    }
+
+    /// Checks that `[T; cap]` fits in `isize::MAX` bytes once rounded up to its alignment.
+    fn layout_for(cap: usize) -> Result<Layout, TryReserveError> {
+        // Layout::array already rejects sizes over isize::MAX after rounding to the alignment
+        Layout::array::<T>(cap).map_err(|_| TryReserveError::CapacityOverflow)
+    }
+
+    /// The layout the current allocation was made with.
+    fn current_layout(&self) -> Option<Layout> {
+        if std::mem::size_of::<T>() == 0 || self.cap == 0 {
+            None
+        } else {
+            //SAFETY: the same layout was checked when the buffer was allocated
+            Some(unsafe { Layout::array::<T>(self.cap).unwrap_unchecked() })
+        }
+    }
+
+    /// Moves the buffer to an allocation of exactly `new_cap` elements.
+    ///
+    /// A capacity of zero frees the buffer and goes back to the dangling pointer.
+    /// On error the buffer is left untouched.
+    fn try_reallocate(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        // zero-sized types are never allocated, so only their length can run out
+        if std::mem::size_of::<T>() == 0 {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        if new_cap == self.cap {
+            return Ok(());
+        }
+
+        if new_cap == 0 {
+            if let Some(layout) = self.current_layout() {
+                /*SAFETY:
+                    ptr was allocated via this allocator
+                    layout is the same layout that was used to allocate ptr
+                */
+                unsafe { self.alloc.deallocate(self.ptr.cast(), layout) };
+            }
+            self.ptr = ptr::NonNull::dangling();
+            self.cap = 0;
+            return Ok(());
+        }
+
+        let new_layout = Self::layout_for(new_cap)?;
+        let result = match self.current_layout() {
+            None => self.alloc.allocate(new_layout),
+            /*SAFETY:
+                ptr was allocated via this allocator
+                old_layout is the same layout that was used to allocate ptr
+                new_layout has the same alignment and a non-zero size that fits in isize
+            */
+            Some(old_layout) => unsafe {
+                if new_cap > self.cap {
+                    self.alloc.grow(self.ptr.cast(), old_layout, new_layout)
+                } else {
+                    self.alloc.shrink(self.ptr.cast(), old_layout, new_layout)
+                }
+            },
+        };
+        self.ptr = result
+            .map_err(|_| TryReserveError::AllocError { layout: new_layout })?
+            .cast();
+        self.cap = new_cap;
+        Ok(())
+    }
+}
+
+impl<T, A: LeAllocator> Drop for RawLeVec<T, A> {
+    fn drop(&mut self) {
+        // nothing was allocated for an empty buffer or for zero-sized types
+        if let Some(layout) = self.current_layout() {
+            /*
+               SAFETY:
+                   ptr was allocated via this allocator
+                   layout is the same layout that was used to allocate ptr
+            */
+            unsafe { self.alloc.deallocate(self.ptr.cast(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{alloc::Layout, cell::RefCell, ptr};
+
+    use super::RawLeVec;
+    use crate::{AllocError, Doubling, Global, LeAllocator};
+
+    /// Checks that every block is freed or resized with the layout it was allocated with.
+    #[derive(Default)]
+    struct Strict {
+        live: RefCell<Vec<(*mut u8, Layout)>>,
+    }
+
+    impl Strict {
+        fn take(&self, ptr: ptr::NonNull<u8>, layout: Layout) {
+            let mut live = self.live.borrow_mut();
+            let index = live
+                .iter()
+                .position(|&block| block == (ptr.as_ptr(), layout))
+                .expect("block was not allocated with this layout");
+            live.swap_remove(index);
+        }
+    }
+
+    unsafe impl LeAllocator for Strict {
+        fn allocate(&self, layout: Layout) -> Result<ptr::NonNull<u8>, AllocError> {
+            assert_ne!(layout.size(), 0);
+            let ptr = Global.allocate(layout)?;
+            self.live.borrow_mut().push((ptr.as_ptr(), layout));
+            Ok(ptr)
+        }
+
+        unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: Layout) {
+            self.take(ptr, layout);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn test_layouts_match() {
+        let strict = Strict::default();
+
+        let mut buf = RawLeVec::<u32, _>::new_in(&strict);
+        buf.reserve::<Doubling>(0, 1);
+        assert_eq!(buf.capacity(), 4);
+        buf.grow_one::<Doubling>();
+        assert_eq!(buf.capacity(), 8);
+        buf.reserve_exact(8, 5);
+        assert_eq!(buf.capacity(), 13);
+        buf.shrink_to(2);
+        assert_eq!(buf.capacity(), 2);
+        buf.shrink_to(0);
+        assert_eq!(buf.capacity(), 0);
+        assert!(strict.live.borrow().is_empty());
+
+        buf.reserve::<Doubling>(0, 3);
+        drop(buf);
+        assert!(strict.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_nothing_allocated() {
+        let strict = Strict::default();
+
+        // dropping a buffer that never allocated must not free the dangling pointer
+        drop(RawLeVec::<u64, _>::new_in(&strict));
+
+        let mut buf = RawLeVec::<(), _>::with_capacity_in(10, &strict);
+        buf.reserve::<Doubling>(10, 100);
+        buf.shrink_to(0);
+        assert_eq!(buf.capacity(), usize::MAX);
+        drop(buf);
+
+        drop(RawLeVec::<u8, _>::with_capacity_zeroed_in(0, &strict));
+        assert!(strict.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_zeroed() {
+        let buf = RawLeVec::<u64, _>::with_capacity_zeroed_in(16, Global);
+        assert_eq!(buf.capacity(), 16);
+        //SAFETY: the 16 slots were zeroed on allocation
+        let values = unsafe { std::slice::from_raw_parts(buf.ptr(), 16) };
+        assert!(values.iter().all(|&value| value == 0));
+    }
+}
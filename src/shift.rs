@@ -0,0 +1,149 @@
+//! Element moves shared by the containers that keep their elements in one contiguous run.
+//!
+//! The helpers work on a base pointer and the length it is paired with, so inline storage
+//! never has to be reborrowed through `&mut self` while elements of it are borrowed.
+
+use std::{mem, ptr, slice};
+
+/// Keeps the elements of `base[..*len]` for which `f` returns `true`, in their original order.
+///
+/// `*len` is zero while `f` runs. Afterwards it covers the kept elements, also when `f` or a
+/// destructor panics, in which case the elements that were not visited yet are kept too.
+///
+/// # Safety
+///
+/// `base` must point to `*len` initialized elements that are not accessed through any other
+/// pointer while this runs.
+pub(crate) unsafe fn retain_mut<T, F: FnMut(&mut T) -> bool>(
+    base: *mut T,
+    len: &mut usize,
+    mut f: F,
+) {
+    // closes the gap left by deleted elements, even while unwinding
+    struct BackshiftOnDrop<'a, T> {
+        base: *mut T,
+        len: &'a mut usize,
+        processed: usize,
+        deleted: usize,
+        original_len: usize,
+    }
+
+    impl<T> Drop for BackshiftOnDrop<'_, T> {
+        fn drop(&mut self) {
+            if self.deleted > 0 {
+                //SAFETY: the unprocessed tail is initialized and the gap before it is free
+                unsafe {
+                    ptr::copy(
+                        self.base.add(self.processed),
+                        self.base.add(self.processed - self.deleted),
+                        self.original_len - self.processed,
+                    );
+                }
+            }
+            *self.len = self.original_len - self.deleted;
+        }
+    }
+
+    let original_len = *len;
+    // nothing is visible to Drop while the buffer has holes, the guard restores the length
+    *len = 0;
+
+    let mut guard = BackshiftOnDrop {
+        base,
+        len,
+        processed: 0,
+        deleted: 0,
+        original_len,
+    };
+
+    while guard.processed != original_len {
+        //SAFETY: processed < original_len and that element was not moved or dropped yet
+        let current = unsafe { &mut *base.add(guard.processed) };
+        if !f(current) {
+            // counted before dropping so a panicking destructor is not run twice
+            guard.processed += 1;
+            guard.deleted += 1;
+            //SAFETY: the element is never touched again
+            unsafe { ptr::drop_in_place(current) };
+            continue;
+        }
+
+        if guard.deleted > 0 {
+            //SAFETY: the hole is behind the current element, so they never overlap
+            unsafe {
+                let hole = base.add(guard.processed - guard.deleted);
+                ptr::copy_nonoverlapping(current, hole, 1);
+            }
+        }
+        guard.processed += 1;
+    }
+}
+
+/// Finishes a drain: drops the elements `remaining` did not yield and moves the `tail_len`
+/// elements at `tail_start` back to `*len`, which then grows over them.
+///
+/// The tail is moved even if one of the remaining elements panics while dropping.
+///
+/// # Safety
+///
+/// Unless `remaining` is empty, it must have been derived from `base` and iterate over
+/// initialized elements of that buffer that are never read again. An empty `remaining` may
+/// point anywhere. `tail_start..tail_start + tail_len` must be initialized elements of the
+/// buffer at `base` that start at or after `*len`.
+pub(crate) unsafe fn finish_drain<T>(
+    base: *mut T,
+    len: &mut usize,
+    remaining: slice::Iter<'_, T>,
+    tail_start: usize,
+    tail_len: usize,
+) {
+    // moves the tail back even if one of the remaining elements panics while dropping
+    struct MoveTailOnDrop<'a, T> {
+        base: *mut T,
+        len: &'a mut usize,
+        tail_start: usize,
+        tail_len: usize,
+    }
+
+    impl<T> Drop for MoveTailOnDrop<'_, T> {
+        fn drop(&mut self) {
+            let start = *self.len;
+            if self.tail_len > 0 && self.tail_start != start {
+                //SAFETY: both ranges are inside the buffer, the tail is initialized
+                unsafe {
+                    ptr::copy(
+                        self.base.add(self.tail_start),
+                        self.base.add(start),
+                        self.tail_len,
+                    );
+                }
+            }
+            *self.len = start + self.tail_len;
+        }
+    }
+
+    let remaining = remaining.as_slice();
+    let _guard = MoveTailOnDrop {
+        base,
+        len,
+        tail_start,
+        tail_len,
+    };
+    if remaining.is_empty() {
+        return;
+    }
+
+    // rebuild the remaining range from the buffer pointer, the iterator only grants shared
+    // access to it
+    let offset = if mem::size_of::<T>() == 0 {
+        0
+    } else {
+        //SAFETY: the remaining elements are inside the buffer `base` points to
+        unsafe { remaining.as_ptr().offset_from(base) as usize }
+    };
+    //SAFETY: `offset + remaining.len()` stays inside the buffer
+    let remaining = ptr::slice_from_raw_parts_mut(unsafe { base.add(offset) }, remaining.len());
+
+    //SAFETY: the remaining elements were not yielded and will not be read again
+    unsafe { ptr::drop_in_place(remaining) };
+}
@@ -0,0 +1,664 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut, Index, IndexMut},
+    ptr,
+    slice::SliceIndex,
+};
+
+use crate::{IntoIter, LeVec};
+
+/// A vector that keeps its first `N` elements inline and moves them to a heap [`LeVec`]
+/// buffer once it outgrows them.
+///
+/// Once spilled the elements stay on the heap, even if the vector shrinks again, so
+/// converting to and from a [`LeVec`] never copies a spilled buffer.
+///
+/// ```
+/// use le_vec::{LeVec, SmallLeVec};
+///
+/// let mut vec: SmallLeVec<i32, 2> = SmallLeVec::new();
+/// vec.push(1);
+/// vec.push(2);
+/// assert!(!vec.spilled());
+///
+/// vec.push(3);
+/// assert!(vec.spilled());
+/// assert_eq!(vec, [1, 2, 3]);
+///
+/// let vec = LeVec::from(vec);
+/// assert_eq!(vec, [1, 2, 3]);
+/// ```
+pub struct SmallLeVec<T, const N: usize> {
+    data: SmallData<T, N>,
+}
+
+enum SmallData<T, const N: usize> {
+    /// The first `len` slots of `buf` are initialized.
+    Inline {
+        buf: [MaybeUninit<T>; N],
+        len: usize,
+    },
+    Heap(LeVec<T>),
+}
+
+impl<T, const N: usize> SmallLeVec<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            data: SmallData::Inline {
+                buf: [const { MaybeUninit::uninit() }; N],
+                len: 0,
+            },
+        }
+    }
+
+    /// Creates an empty vector with room for at least `capacity` elements, which is already
+    /// spilled if `capacity` is greater than `N`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity > N {
+            Self {
+                data: SmallData::Heap(LeVec::with_capacity(capacity)),
+            }
+        } else {
+            Self::new()
+        }
+    }
+
+    /// Returns `true` once the elements have moved to the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.data, SmallData::Heap(_))
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.data {
+            SmallData::Inline { len, .. } => *len,
+            SmallData::Heap(vec) => vec.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `N` until the vector spills, then the capacity of the heap buffer.
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            SmallData::Inline { .. } => N,
+            SmallData::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    /// Reserves room for at least `additional` more elements, spilling if they do not fit
+    /// inline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes or the allocation fails.
+    pub fn reserve(&mut self, additional: usize) {
+        match &mut self.data {
+            SmallData::Inline { len, .. } => {
+                let required = len.checked_add(additional).expect("capacity overflow");
+                if required > N {
+                    self.spill(required.max(N.saturating_mul(2)));
+                }
+            }
+            SmallData::Heap(vec) => vec.reserve(additional),
+        }
+    }
+
+    /// Moves the inline elements to a heap buffer with room for `capacity` elements.
+    fn spill(&mut self, capacity: usize) {
+        let SmallData::Inline { buf, len } = &mut self.data else {
+            return;
+        };
+
+        let mut vec = LeVec::with_capacity(capacity.max(*len));
+        //SAFETY: the first len inline slots are initialized and vec has room for them, the
+        //inline buffer never drops anything so the elements are only owned by vec afterwards
+        unsafe {
+            ptr::copy_nonoverlapping(buf.as_ptr().cast::<T>(), vec.as_mut_ptr(), *len);
+            vec.set_len(*len);
+        }
+        self.data = SmallData::Heap(vec);
+    }
+
+    /// Returns the start of the storage, the length and the capacity, for the operations
+    /// that work the same inline and on the heap.
+    fn raw_parts_mut(&mut self) -> (*mut T, &mut usize, usize) {
+        match &mut self.data {
+            SmallData::Inline { buf, len } => (buf.as_mut_ptr().cast(), len, N),
+            SmallData::Heap(vec) => (vec.buf.ptr(), &mut vec.len, vec.buf.capacity()),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        let (_, len, cap) = self.raw_parts_mut();
+        if *len == cap {
+            self.reserve(1);
+        }
+
+        let (ptr, len, _) = self.raw_parts_mut();
+        //SAFETY: len is less than capacity, so the slot is available and uninitialized
+        unsafe { ptr.add(*len).write(value) };
+        *len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let (ptr, len, _) = self.raw_parts_mut();
+        if *len == 0 {
+            return None;
+        }
+
+        *len -= 1;
+        //SAFETY: the slot was initialized and is no longer covered by the length
+        Some(unsafe { ptr.add(*len).read() })
+    }
+
+    /// Inserts `value` at `index`, shifting everything after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+
+        self.reserve(1);
+        let (ptr, len, _) = self.raw_parts_mut();
+        //SAFETY: index <= len < capacity, so both the shifted range and the slot are available
+        unsafe {
+            let slot = ptr.add(index);
+            ptr::copy(slot, slot.add(1), *len - index);
+            slot.write(value);
+        }
+        *len += 1;
+    }
+
+    /// Removes and returns the element at `index`, shifting everything after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        let (ptr, len, _) = self.raw_parts_mut();
+        assert!(
+            index < *len,
+            "removal index (is {index}) should be < len (is {})",
+            *len
+        );
+
+        //SAFETY: index is in bounds, the removed element is read once before being overwritten
+        unsafe {
+            let slot = ptr.add(index);
+            let value = slot.read();
+            ptr::copy(slot.add(1), slot, *len - index - 1);
+            *len -= 1;
+            value
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let (ptr, len, _) = self.raw_parts_mut();
+        assert!(
+            index < *len,
+            "swap_remove index (is {index}) should be < len (is {})",
+            *len
+        );
+
+        //SAFETY: index and len - 1 are both in bounds, the removed element is read once
+        unsafe {
+            let value = ptr.add(index).read();
+            ptr::copy(ptr.add(*len - 1), ptr.add(index), 1);
+            *len -= 1;
+            value
+        }
+    }
+
+    /// Shortens the vector to `new_len` elements, dropping the rest. Does nothing if `new_len`
+    /// is not lower than the current length.
+    pub fn truncate(&mut self, new_len: usize) {
+        let (ptr, len, _) = self.raw_parts_mut();
+        if new_len >= *len {
+            return;
+        }
+
+        let remaining = *len - new_len;
+        //SAFETY: new_len..len is initialized and is no longer visible once the length shrinks
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(ptr.add(new_len), remaining);
+            *len = new_len;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops every element, keeping the storage.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        match &self.data {
+            SmallData::Inline { buf, .. } => buf.as_ptr().cast(),
+            SmallData::Heap(vec) => vec.as_ptr(),
+        }
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.raw_parts_mut().0
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        //SAFETY: the first len elements are initialized
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let (ptr, len, _) = self.raw_parts_mut();
+        //SAFETY: the first len elements are initialized
+        unsafe { std::slice::from_raw_parts_mut(ptr, *len) }
+    }
+}
+
+impl<T, const N: usize> Drop for SmallLeVec<T, N> {
+    fn drop(&mut self) {
+        // a heap buffer drops its own elements
+        if let SmallData::Inline { .. } = self.data {
+            //SAFETY: the first len inline elements are initialized
+            unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+        }
+    }
+}
+
+impl<T, const N: usize> From<LeVec<T>> for SmallLeVec<T, N> {
+    /// Takes over the buffer of `vec` as a spilled vector, without copying. A vector that
+    /// never allocated starts out inline instead.
+    fn from(vec: LeVec<T>) -> Self {
+        if vec.capacity() == 0 {
+            Self::new()
+        } else {
+            Self {
+                data: SmallData::Heap(vec),
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> From<SmallLeVec<T, N>> for LeVec<T> {
+    /// Hands back the heap buffer of a spilled vector without copying, inline elements are
+    /// moved to a new allocation.
+    fn from(vec: SmallLeVec<T, N>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        vec.spill(0);
+        match &vec.data {
+            //SAFETY: vec is never dropped, so the buffer is moved out exactly once
+            SmallData::Heap(heap) => unsafe { ptr::read(heap) },
+            SmallData::Inline { .. } => unreachable!("the vector was just spilled"),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallLeVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallLeVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallLeVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for SmallLeVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+impl_slice_eq! { [const N: usize, const M: usize] SmallLeVec<T, N>, SmallLeVec<U, M> }
+impl_slice_eq! { [const N: usize] SmallLeVec<T, N>, [U] }
+impl_slice_eq! { [const N: usize] SmallLeVec<T, N>, &[U] }
+impl_slice_eq! { [const N: usize, const M: usize] SmallLeVec<T, N>, [U; M] }
+
+impl<T: Eq, const N: usize> Eq for SmallLeVec<T, N> {}
+
+impl<T, const N: usize> AsRef<[T]> for SmallLeVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for SmallLeVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Deref for SmallLeVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallLeVec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> Index<I> for SmallLeVec<T, N> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> IndexMut<I> for SmallLeVec<T, N> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallLeVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallLeVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallLeVec<T, N> {
+    type Item = T;
+    type IntoIter = SmallIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let vec = ManuallyDrop::new(self);
+        //SAFETY: vec is never dropped, so the storage is moved out exactly once
+        let data = match unsafe { ptr::read(&vec.data) } {
+            SmallData::Inline { buf, len } => SmallIntoIterData::Inline {
+                buf,
+                start: 0,
+                end: len,
+            },
+            SmallData::Heap(heap) => SmallIntoIterData::Heap(heap.into_iter()),
+        };
+        SmallIntoIter { data }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallLeVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallLeVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An owning iterator over the elements of a [`SmallLeVec`], yielded front to back.
+pub struct SmallIntoIter<T, const N: usize> {
+    data: SmallIntoIterData<T, N>,
+}
+
+enum SmallIntoIterData<T, const N: usize> {
+    /// `buf[start..end]` are the elements that were not yielded yet.
+    Inline {
+        buf: [MaybeUninit<T>; N],
+        start: usize,
+        end: usize,
+    },
+    Heap(IntoIter<T>),
+}
+
+impl<T, const N: usize> SmallIntoIter<T, N> {
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.data {
+            //SAFETY: start..end are initialized elements that were not yielded yet
+            SmallIntoIterData::Inline { buf, start, end } => unsafe {
+                std::slice::from_raw_parts(buf.as_ptr().add(*start).cast(), end - start)
+            },
+            SmallIntoIterData::Heap(iter) => iter.as_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for SmallIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.data {
+            SmallIntoIterData::Inline { buf, start, end } => {
+                if start == end {
+                    return None;
+                }
+                //SAFETY: start is initialized and not yielded yet, it is skipped from now on
+                let value = unsafe { buf[*start].assume_init_read() };
+                *start += 1;
+                Some(value)
+            }
+            SmallIntoIterData::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.as_slice().len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for SmallIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.data {
+            SmallIntoIterData::Inline { buf, start, end } => {
+                if start == end {
+                    return None;
+                }
+                *end -= 1;
+                //SAFETY: end is initialized and not yielded yet, it is skipped from now on
+                Some(unsafe { buf[*end].assume_init_read() })
+            }
+            SmallIntoIterData::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for SmallIntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for SmallIntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for SmallIntoIter<T, N> {
+    fn drop(&mut self) {
+        if let SmallIntoIterData::Inline { buf, start, end } = &mut self.data {
+            //SAFETY: start..end are initialized elements that were not yielded yet
+            unsafe {
+                let rest = ptr::slice_from_raw_parts_mut(
+                    buf.as_mut_ptr().add(*start).cast::<T>(),
+                    *end - *start,
+                );
+                *start = *end;
+                ptr::drop_in_place(rest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_spill() {
+        let mut vec: SmallLeVec<u32, 4> = SmallLeVec::new();
+        assert_eq!(vec.capacity(), 4);
+        vec.extend(0..4);
+        assert!(!vec.spilled());
+        assert_eq!(vec, [0, 1, 2, 3]);
+
+        vec.push(4);
+        assert!(vec.spilled());
+        assert_eq!(vec.capacity(), 8);
+        assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+        // once spilled the buffer stays on the heap
+        vec.clear();
+        assert!(vec.spilled());
+
+        let vec: SmallLeVec<u32, 4> = SmallLeVec::with_capacity(10);
+        assert!(vec.spilled());
+        assert!(vec.capacity() >= 10);
+
+        let mut vec: SmallLeVec<u32, 0> = SmallLeVec::new();
+        vec.push(1);
+        assert!(vec.spilled());
+        assert_eq!(vec, [1]);
+    }
+
+    #[test]
+    fn test_vec_api() {
+        let mut vec: SmallLeVec<String, 3> = SmallLeVec::new();
+        vec.push("b".to_string());
+        vec.insert(0, "a".to_string());
+        vec.insert(2, "d".to_string());
+        assert_eq!(vec, ["a", "b", "d"]);
+        assert_eq!(vec.get(1).map(String::as_str), Some("b"));
+        assert_eq!(vec[..2], ["a", "b"]);
+
+        vec.insert(2, "c".to_string());
+        assert!(vec.spilled());
+        assert_eq!(vec, ["a", "b", "c", "d"]);
+
+        assert_eq!(vec.remove(0), "a");
+        assert_eq!(vec.swap_remove(0), "b");
+        assert_eq!(vec, ["d", "c"]);
+        vec[0].push('!');
+        assert_eq!(vec.pop().as_deref(), Some("c"));
+        assert_eq!(vec.pop().as_deref(), Some("d!"));
+        assert_eq!(vec.pop(), None);
+
+        let vec: SmallLeVec<i32, 2> = [3, 1, 2].into_iter().collect();
+        assert_eq!(vec.clone(), vec);
+        assert_eq!(format!("{vec:?}"), "[3, 1, 2]");
+        assert_eq!(vec.iter().sum::<i32>(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+    fn test_insert_out_of_bounds() {
+        let mut vec: SmallLeVec<i32, 2> = SmallLeVec::new();
+        vec.push(1);
+        vec.insert(2, 2);
+    }
+
+    #[test]
+    fn test_drop() {
+        let counter = Rc::new(());
+        let mut vec: SmallLeVec<Rc<()>, 2> = SmallLeVec::new();
+        vec.push(counter.clone());
+        vec.push(counter.clone());
+        vec.truncate(1);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let vec: SmallLeVec<Rc<()>, 4> = (0..3).map(|_| counter.clone()).collect();
+        let mut iter = vec.into_iter();
+        iter.next_back();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let vec: SmallLeVec<Rc<()>, 1> = (0..3).map(|_| counter.clone()).collect();
+        let mut iter = vec.into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let vec: SmallLeVec<i32, 4> = (1..=3).collect();
+        assert_eq!(vec.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+
+        let vec: SmallLeVec<i32, 2> = (1..=3).collect();
+        assert_eq!(vec.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_le_vec_conversion() {
+        let mut vec = LeVec::with_capacity(8);
+        vec.extend_from_copy_slice(&[1, 2, 3]);
+        let ptr = vec.as_ptr();
+
+        let small: SmallLeVec<i32, 4> = vec.into();
+        assert!(small.spilled());
+        assert_eq!(small.as_ptr(), ptr);
+
+        let vec = LeVec::from(small);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec, [1, 2, 3]);
+
+        let small: SmallLeVec<i32, 4> = LeVec::new().into();
+        assert!(!small.spilled());
+
+        let small: SmallLeVec<String, 4> = ["a".to_string(), "b".to_string()].into_iter().collect();
+        let vec = LeVec::from(small);
+        assert_eq!(vec, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut vec: SmallLeVec<(), 2> = SmallLeVec::new();
+        for _ in 0..5 {
+            vec.push(());
+        }
+        assert!(vec.spilled());
+        assert_eq!(vec.len(), 5);
+        assert_eq!(vec.pop(), Some(()));
+        assert_eq!(vec.into_iter().count(), 4);
+    }
+}
@@ -0,0 +1,557 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, Deref, RangeBounds},
+    ptr,
+};
+
+use crate::{IntoIter, LeVec};
+
+/// A [`LeVec`] that keeps its elements in ascending order.
+///
+/// Equal elements are allowed and stay in the order they were added. Lookups are binary
+/// searches, and the set operations merge both vectors in a single pass, treating them as
+/// multisets.
+///
+/// ```
+/// use le_vec::SortedLeVec;
+///
+/// let mut table: SortedLeVec<i32> = [5, 1, 3].into_iter().collect();
+/// assert_eq!(table.insert(4), 2);
+/// assert_eq!(table.as_slice(), [1, 3, 4, 5]);
+/// assert_eq!(table.range(2..5), [3, 4]);
+/// assert_eq!(table.rank(&4), 2);
+///
+/// let other: SortedLeVec<i32> = [3, 6].into_iter().collect();
+/// assert_eq!(table.union(&other).as_slice(), [1, 3, 4, 5, 6]);
+/// ```
+pub struct SortedLeVec<T> {
+    vec: LeVec<T>,
+}
+
+impl<T: Ord> SortedLeVec<T> {
+    pub fn new() -> Self {
+        Self { vec: LeVec::new() }
+    }
+
+    /// Creates an empty vector with room for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            vec: LeVec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.vec.as_slice()
+    }
+
+    /// Inserts `value` after every element that is not greater than it, and returns the
+    /// position it ended up at.
+    pub fn insert(&mut self, value: T) -> usize {
+        let index = self.vec.partition_point(|current| current <= &value);
+        self.vec.insert(index, value);
+        index
+    }
+
+    /// Removes and returns the first element equal to `value`, if there is one.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let index = self.rank(value);
+        if self.vec.get(index) == Some(value) {
+            Some(self.vec.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes and returns the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove_index(&mut self, index: usize) -> T {
+        self.vec.remove(index)
+    }
+
+    /// Removes and returns the greatest element.
+    pub fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.vec.binary_search(value).is_ok()
+    }
+
+    /// Returns the number of elements less than `value`, which is also the position of the
+    /// first element not less than it.
+    pub fn rank(&self, value: &T) -> usize {
+        self.vec.partition_point(|current| current < value)
+    }
+
+    /// Returns the `k`-th smallest element, counting from 0.
+    pub fn select(&self, k: usize) -> Option<&T> {
+        self.vec.get(k)
+    }
+
+    /// Returns the elements that fall inside `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> &[T] {
+        if let (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) = (range.start_bound(), range.end_bound())
+        {
+            assert!(
+                start <= end,
+                "range start is greater than range end in SortedLeVec"
+            );
+        }
+
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.vec.partition_point(|current| current < start),
+            Bound::Excluded(start) => self.vec.partition_point(|current| current <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.vec.partition_point(|current| current <= end),
+            Bound::Excluded(end) => self.vec.partition_point(|current| current < end),
+            Bound::Unbounded => self.vec.len(),
+        };
+        // both ends excluding the same value leave end before start
+        &self.vec[start..end.max(start)]
+    }
+
+    /// Keeps only the elements for which `f` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.vec.retain(f);
+    }
+
+    /// Removes consecutive equal elements, leaving every value once.
+    pub fn dedup(&mut self) {
+        self.vec.dedup();
+    }
+
+    /// Shortens the vector to its `len` smallest elements.
+    pub fn truncate(&mut self, len: usize) {
+        self.vec.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    /// Moves every element of `other` in, merging both in a single pass.
+    ///
+    /// If comparing two elements panics, both vectors stay sorted and no element is lost,
+    /// but the elements merged so far have already moved from `other` to `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.merge_in(&mut other.vec);
+    }
+
+    /// Merges the sorted `other` into the vector, its elements going after equal ones, and
+    /// leaves `other` empty.
+    ///
+    /// The merge runs backwards from the end of the spare capacity, so the only allocation
+    /// is the room for `other`.
+    fn merge_in(&mut self, other: &mut LeVec<T>) {
+        // closes the gap between the unmerged elements and the merged ones, even while
+        // unwinding; the merged ones are never smaller than what is left on either side
+        struct MergeOnDrop<'a, T> {
+            vec: &'a mut LeVec<T>,
+            other: &'a mut LeVec<T>,
+            left: usize,
+            right: usize,
+            total: usize,
+        }
+
+        impl<T> Drop for MergeOnDrop<'_, T> {
+            fn drop(&mut self) {
+                let merged_start = self.left + self.right;
+                let merged = self.total - merged_start;
+                //SAFETY: the merged elements are initialized and the gap before them is free
+                unsafe {
+                    let base = self.vec.as_mut_ptr();
+                    ptr::copy(base.add(merged_start), base.add(self.left), merged);
+                    self.vec.set_len(self.left + merged);
+                    self.other.set_len(self.right);
+                }
+            }
+        }
+
+        if other.is_empty() {
+            return;
+        }
+
+        self.vec.reserve(other.len());
+        let (left, right) = (self.vec.len(), other.len());
+        let (a, b) = (self.vec.as_mut_ptr(), other.as_mut_ptr());
+        //SAFETY: the guard owns the elements from here on and sets both lengths again
+        unsafe {
+            self.vec.set_len(0);
+            other.set_len(0);
+        }
+
+        let mut guard = MergeOnDrop {
+            vec: &mut self.vec,
+            other,
+            left,
+            right,
+            total: left + right,
+        };
+
+        while guard.left > 0 && guard.right > 0 {
+            /*
+               SAFETY:
+                   left and right count the unmerged elements at the front of each buffer
+                   the slot in front of the merged elements is free and differs from both
+            */
+            unsafe {
+                let (x, y) = (a.add(guard.left - 1), b.add(guard.right - 1));
+                let out = a.add(guard.left + guard.right - 1);
+                if *y < *x {
+                    ptr::copy_nonoverlapping(x, out, 1);
+                    guard.left -= 1;
+                } else {
+                    ptr::copy_nonoverlapping(y, out, 1);
+                    guard.right -= 1;
+                }
+            }
+        }
+
+        // what is left of `other` goes in front, where `self` has nothing left
+        //SAFETY: either nothing is left of `other` or the first `right` slots are free
+        unsafe { ptr::copy_nonoverlapping(b, a, guard.right) };
+        guard.right = 0;
+    }
+
+    /// Walks both vectors in order, cloning the elements only found on the left, the ones
+    /// found on both sides (once) and the ones only found on the right, as asked.
+    fn merge_by(&self, other: &Self, left_only: bool, both: bool, right_only: bool) -> Self
+    where
+        T: Clone,
+    {
+        let (a, b) = (self.as_slice(), other.as_slice());
+        let mut vec = LeVec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                Ordering::Less => {
+                    if left_only {
+                        vec.push(a[i].clone());
+                    }
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    if right_only {
+                        vec.push(b[j].clone());
+                    }
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    if both {
+                        vec.push(a[i].clone());
+                    }
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        if left_only {
+            vec.extend_from_slice(&a[i..]);
+        }
+        if right_only {
+            vec.extend_from_slice(&b[j..]);
+        }
+        Self { vec }
+    }
+
+    /// Returns the elements found in either vector.
+    pub fn union(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        self.merge_by(other, true, true, true)
+    }
+
+    /// Returns the elements found in both vectors.
+    pub fn intersection(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        self.merge_by(other, false, true, false)
+    }
+
+    /// Returns the elements found in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        self.merge_by(other, true, false, false)
+    }
+
+    /// Returns the elements found in exactly one of the vectors.
+    pub fn symmetric_difference(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        self.merge_by(other, true, false, true)
+    }
+}
+
+impl<T> Deref for SortedLeVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.vec.as_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for SortedLeVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Ord> From<LeVec<T>> for SortedLeVec<T> {
+    /// Sorts the vector in place, keeping equal elements in their order.
+    fn from(mut vec: LeVec<T>) -> Self {
+        vec.sort();
+        Self { vec }
+    }
+}
+
+impl<T> From<SortedLeVec<T>> for LeVec<T> {
+    fn from(sorted: SortedLeVec<T>) -> Self {
+        sorted.vec
+    }
+}
+
+impl<T: Ord> Default for SortedLeVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for SortedLeVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SortedLeVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.vec.as_slice(), f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for SortedLeVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+impl<T: Eq> Eq for SortedLeVec<T> {}
+
+impl<T: Ord> FromIterator<T> for SortedLeVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        LeVec::from_iter(iter).into()
+    }
+}
+
+impl<T: Ord> Extend<T> for SortedLeVec<T> {
+    /// Sorts the new elements on their own and merges them in, instead of inserting them
+    /// one by one.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut new = LeVec::from_iter(iter);
+        new.sort();
+        self.merge_in(&mut new);
+    }
+}
+
+impl<T> IntoIterator for SortedLeVec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedLeVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sorted(values: &[i32]) -> SortedLeVec<i32> {
+        values.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut vec = SortedLeVec::new();
+        assert_eq!(vec.insert(5), 0);
+        assert_eq!(vec.insert(1), 0);
+        assert_eq!(vec.insert(9), 2);
+        // equal elements go after the ones already there
+        assert_eq!(vec.insert(5), 2);
+        assert_eq!(vec.as_slice(), [1, 5, 5, 9]);
+
+        assert!(vec.contains(&9));
+        assert!(!vec.contains(&4));
+        assert_eq!(vec.remove(&5), Some(5));
+        assert_eq!(vec.remove(&4), None);
+        assert_eq!(vec.remove_index(0), 1);
+        assert_eq!(vec.pop(), Some(9));
+        assert_eq!(vec.as_slice(), [5]);
+    }
+
+    #[test]
+    fn test_stable_order() {
+        #[derive(Debug)]
+        struct Keyed(i32, &'static str);
+
+        impl PartialEq for Keyed {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Keyed {}
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut vec: SortedLeVec<Keyed> = [Keyed(2, "a"), Keyed(1, "b"), Keyed(2, "c")]
+            .into_iter()
+            .collect();
+        vec.insert(Keyed(2, "d"));
+        vec.extend([Keyed(2, "e"), Keyed(1, "f")]);
+
+        let names: Vec<_> = vec.iter().map(|keyed| keyed.1).collect();
+        assert_eq!(names, ["b", "f", "a", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn test_range_rank_select() {
+        let vec = sorted(&[1, 3, 3, 5, 7, 9]);
+        assert_eq!(vec.range(3..7), [3, 3, 5]);
+        assert_eq!(vec.range(3..=7), [3, 3, 5, 7]);
+        assert_eq!(vec.range((Bound::Excluded(3), Bound::Unbounded)), [5, 7, 9]);
+        assert_eq!(vec.range(..4), [1, 3, 3]);
+        assert_eq!(vec.range(4..5), []);
+        assert_eq!(vec.range(4..4), []);
+        assert_eq!(vec.range((Bound::Excluded(3), Bound::Excluded(3))), []);
+
+        assert_eq!(vec.rank(&0), 0);
+        assert_eq!(vec.rank(&3), 1);
+        assert_eq!(vec.rank(&4), 3);
+        assert_eq!(vec.rank(&10), 6);
+        assert_eq!(vec.select(3), Some(&5));
+        assert_eq!(vec.select(6), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end in SortedLeVec")]
+    fn test_range_reversed() {
+        let vec = sorted(&[1, 2, 3]);
+        #[allow(clippy::reversed_empty_ranges)]
+        vec.range(3..=1);
+    }
+
+    #[test]
+    fn test_extend_and_append() {
+        let mut vec = sorted(&[2, 4, 6]);
+        vec.extend([5, 1, 7, 4]);
+        assert_eq!(vec.as_slice(), [1, 2, 4, 4, 5, 6, 7]);
+
+        let mut other = sorted(&[0, 8]);
+        vec.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(vec.as_slice(), [0, 1, 2, 4, 4, 5, 6, 7, 8]);
+
+        vec.dedup();
+        assert_eq!(vec.len(), 8);
+        assert_eq!(LeVec::from(vec), [0, 1, 2, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = sorted(&[1, 2, 2, 3, 5, 8]);
+        let b = sorted(&[2, 3, 3, 4, 8, 9]);
+
+        assert_eq!(a.union(&b).as_slice(), [1, 2, 2, 3, 3, 4, 5, 8, 9]);
+        assert_eq!(a.intersection(&b).as_slice(), [2, 3, 8]);
+        assert_eq!(a.difference(&b).as_slice(), [1, 2, 5]);
+        assert_eq!(b.difference(&a).as_slice(), [3, 4, 9]);
+        assert_eq!(a.symmetric_difference(&b).as_slice(), [1, 2, 3, 4, 5, 9]);
+
+        let empty = SortedLeVec::new();
+        assert_eq!(a.union(&empty), a);
+        assert!(a.intersection(&empty).is_empty());
+        assert_eq!(empty.difference(&a), empty);
+    }
+
+    #[test]
+    fn test_append_panic() {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Touchy(i32);
+
+        impl PartialOrd for Touchy {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Touchy {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                if self.0 + other.0 == 6 {
+                    panic!("comparison panicked");
+                }
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut vec: SortedLeVec<_> = [1, 4, 9].into_iter().map(Touchy).collect();
+        let mut other: SortedLeVec<_> = [2, 13].into_iter().map(Touchy).collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vec.append(&mut other);
+        }));
+        assert!(result.is_err());
+
+        // 9 and 13 were merged before comparing 4 with 2 panicked, both sides stay sorted
+        assert_eq!(
+            vec.as_slice(),
+            [Touchy(1), Touchy(4), Touchy(9), Touchy(13)]
+        );
+        assert_eq!(other.as_slice(), [Touchy(2)]);
+    }
+}
@@ -0,0 +1,187 @@
+use std::ptr;
+
+use crate::{Doubling, Drain, Global, GrowthPolicy, LeAllocator, LeVec};
+
+/// A splicing iterator for [`LeVec`], created by [`LeVec::splice`].
+///
+/// Yields the removed elements. The replacement is written when the `Splice` is dropped.
+pub struct Splice<'a, I, A = Global, G = Doubling>
+where
+    I: Iterator + 'a,
+    A: LeAllocator + 'a,
+    G: GrowthPolicy + 'a,
+{
+    pub(crate) drain: Drain<'a, I::Item, A, G>,
+    pub(crate) replace_with: I,
+}
+
+impl<I: Iterator, A: LeAllocator, G: GrowthPolicy> Iterator for Splice<'_, I, A, G> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator, A: LeAllocator, G: GrowthPolicy> DoubleEndedIterator for Splice<'_, I, A, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator, A: LeAllocator, G: GrowthPolicy> ExactSizeIterator for Splice<'_, I, A, G> {}
+
+impl<I: Iterator, A: LeAllocator, G: GrowthPolicy> Drop for Splice<'_, I, A, G> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+        // the buffer may move from here on, so the drain must not point into it anymore; an
+        // empty slice at the end of the vector keeps the iterator derived from the buffer
+        //SAFETY: the vector is mutably borrowed for as long as the drain lives
+        self.drain.iter = unsafe {
+            let vec = self.drain.vec.as_mut();
+            std::slice::from_raw_parts(vec.buf.ptr().add(vec.len), 0).iter()
+        };
+
+        //SAFETY: the drained range is empty, so vec.len..tail_start is free to be written
+        unsafe {
+            if self.drain.tail_len == 0 {
+                self.drain.vec.as_mut().extend(self.replace_with.by_ref());
+                return;
+            }
+
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+
+            // with an exact size hint this is the only time the tail moves
+            let (lower_bound, _) = self.replace_with.size_hint();
+            if lower_bound > 0 {
+                self.drain.move_tail(lower_bound);
+                if !self.drain.fill(&mut self.replace_with) {
+                    return;
+                }
+            }
+
+            // the hint was too low, buffer what is left so the tail moves only once more
+            let mut collected = self.replace_with.by_ref().collect::<LeVec<_>>().into_iter();
+            if collected.len() > 0 {
+                self.drain.move_tail(collected.len());
+                let filled = self.drain.fill(&mut collected);
+                debug_assert!(filled);
+                debug_assert_eq!(collected.len(), 0);
+            }
+        }
+    }
+}
+
+impl<T, A: LeAllocator, G: GrowthPolicy> Drain<'_, T, A, G> {
+    /// Writes items from `replace_with` into the gap between `vec.len` and `tail_start`.
+    ///
+    /// Returns `true` if the whole gap was filled.
+    ///
+    /// # Safety
+    ///
+    /// The gap must not hold initialized elements.
+    unsafe fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
+        let vec = self.vec.as_mut();
+        while vec.len < self.tail_start {
+            match replace_with.next() {
+                Some(value) => {
+                    vec.buf.ptr().add(vec.len).write(value);
+                    vec.len += 1;
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Moves the tail `additional` slots to the right, growing the buffer if needed.
+    ///
+    /// # Safety
+    ///
+    /// No references into the buffer may be alive.
+    unsafe fn move_tail(&mut self, additional: usize) {
+        let vec = self.vec.as_mut();
+        let used = self.tail_start + self.tail_len;
+        vec.buf.reserve::<G>(used, additional);
+
+        let new_tail_start = self.tail_start + additional;
+        let base = vec.buf.ptr();
+        ptr::copy(
+            base.add(self.tail_start),
+            base.add(new_tail_start),
+            self.tail_len,
+        );
+        self.tail_start = new_tail_start;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LeVec;
+
+    fn numbers(values: &[i32]) -> LeVec<i32> {
+        let mut vec = LeVec::new();
+        for &value in values {
+            vec.push(value);
+        }
+        vec
+    }
+
+    #[test]
+    fn test_splice_exact() {
+        let mut vec = numbers(&[1, 2, 3, 4, 5]);
+
+        let removed: Vec<_> = vec.splice(1..3, [10, 20, 30, 40]).collect();
+        assert_eq!(removed, [2, 3]);
+        assert_eq!(vec.as_slice(), [1, 10, 20, 30, 40, 4, 5]);
+
+        vec.splice(..5, []);
+        assert_eq!(vec.as_slice(), [4, 5]);
+
+        vec.splice(2.., [6, 7]);
+        assert_eq!(vec.as_slice(), [4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_splice_inexact_hint() {
+        let mut vec = numbers(&[1, 2, 3]);
+
+        // filter reports a lower bound of 0, so the replacement has to be buffered
+        let removed: Vec<_> = vec.splice(1..2, (0..10).filter(|n| n % 3 == 0)).collect();
+        assert_eq!(removed, [2]);
+        assert_eq!(vec.as_slice(), [1, 0, 3, 6, 9, 3]);
+    }
+
+    #[test]
+    fn test_splice_strings() {
+        let mut vec = LeVec::new();
+        for value in ["a", "b", "c"] {
+            vec.push(value.to_string());
+        }
+
+        drop(vec.splice(.., ["x".to_string()]));
+        assert_eq!(vec.as_slice(), ["x"]);
+    }
+
+    #[test]
+    fn test_splice_drop_grows() {
+        let mut vec = LeVec::new();
+        for value in ["a", "b", "c"] {
+            vec.push(value.to_string());
+        }
+
+        // nothing is yielded and the replacement makes the buffer move while the tail is kept
+        drop(vec.splice(1..2, (0..8).map(|n| n.to_string())));
+        assert_eq!(vec.len(), 10);
+        assert_eq!(vec[0], "a");
+        assert_eq!(vec[1], "0");
+        assert_eq!(vec[8], "7");
+        assert_eq!(vec[9], "c");
+    }
+}
@@ -0,0 +1,19 @@
+//! Fixtures shared by the test modules.
+
+use std::rc::Rc;
+
+/// An element that keeps `_counter` alive and panics when dropped if `panics` is set.
+///
+/// The strong count of the shared counter tells how many elements were not dropped yet.
+pub(crate) struct PanicOnDrop {
+    pub(crate) panics: bool,
+    pub(crate) _counter: Rc<()>,
+}
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        if self.panics {
+            panic!("drop panicked");
+        }
+    }
+}